@@ -2,3 +2,54 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+/// Max number of Raydium CPMM configs the admin can whitelist
+pub const MAX_AMM_CONFIGS: usize = 8;
+
+/// Max deviation between the opening pool price and the final curve price
+pub const MIGRATION_PRICE_TOLERANCE_BPS: u64 = 50;
+
+/// Bounds of the on-chain `TokenProfile` fields
+pub const MAX_PROFILE_DESCRIPTION_LENGTH: usize = 280;
+pub const MAX_PROFILE_URL_LENGTH: usize = 100;
+
+/// Base units the curve prices are expressed against, 1B tokens with 9 decimals.
+/// Other supplies and decimals are scaled to it so every curve spans the same market cap
+pub const CURVE_REFERENCE_BASE_UNITS: u64 = 1_000_000_000_000_000_000;
+
+/// Current `TokenState` layout, 0.1.x accounts have no version and are upgraded by
+/// `migrate_token_state`
+pub const TOKEN_STATE_VERSION: u8 = 1;
+
+/// Decimals of a launch token when the creator doesn't pick any, and the max allowed
+pub const DEFAULT_TOKEN_DECIMALS: u8 = 9;
+pub const MAX_TOKEN_DECIMALS: u8 = 9;
+
+/// Default bounds of `CreateTokenArgs::total_supply`, in whole tokens
+pub const DEFAULT_MIN_TOTAL_SUPPLY: u64 = 1_000_000;
+pub const DEFAULT_MAX_TOTAL_SUPPLY: u64 = 1_000_000_000;
+
+/// Mints kept in `CreatorProfile::recent_mints`
+pub const MAX_RECENT_MINTS: usize = 5;
+
+/// Upper bound of `LaunchRateLimit::max_launches`, also the launch timestamps a
+/// `CreatorProfile` keeps
+pub const MAX_LAUNCHES_PER_WINDOW: usize = 10;
+
+/// Max number of creators the admin can exempt from the launch rate limit
+pub const MAX_RATE_LIMIT_EXEMPTIONS: usize = 16;
+
+/// Furthest a launch can schedule its trading start, 7 days
+pub const MAX_TRADING_START_DELAY: i64 = 7 * 24 * 60 * 60;
+
+/// Longest allowlist phase a launch can open with, counted from its trading start
+pub const MAX_ALLOWLIST_DURATION: i64 = 7 * 24 * 60 * 60;
+
+/// Furthest graduation deadline a launch can set, counted from its trading start
+pub const MAX_GRADUATION_DEADLINE: i64 = 90 * 24 * 60 * 60;
+
+/// Default wait between an emergency unwind and the first redemption, 1 day
+pub const DEFAULT_UNWIND_DELAY: i64 = 24 * 60 * 60;
+
+/// Longest unwind delay the admin can configure, 30 days
+pub const MAX_UNWIND_DELAY: i64 = 30 * 24 * 60 * 60;
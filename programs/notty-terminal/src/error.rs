@@ -40,7 +40,7 @@ pub enum NottyTerminalError {
     InsufficientTokenBalance,
     #[msg("Vault has insufficient SOL balance")]
     InsufficientVaultBalance,
-    #[msg("Invalid token ordering - token must be < WSOL")]
+    #[msg("Invalid token pair - pool must pair the launch token with WSOL")]
     InvalidTokenOrdering,
     #[msg("Invalid AMM config")]
     InvalidAmmConfig,
@@ -58,6 +58,120 @@ pub enum NottyTerminalError {
     InvalidStartingMcap,
     #[msg("Invalid Target MCAP should be 450 SOL")]
     InvalidTargetMcap,
+    #[msg("Migration venue is not whitelisted")]
+    VenueNotAllowed,
+    #[msg("Token is set to graduate into a different venue")]
+    WrongVenue,
+    #[msg("Venue does not support the launch token program")]
+    UnsupportedTokenProgram,
+    #[msg("Migration has already been finalized")]
+    AlreadyFinalized,
+    #[msg("Rent receiver doesn't match the configured one")]
+    InvalidRentReceiver,
+    #[msg("Pool opening price deviates from the final curve price")]
+    PriceDiscontinuity,
+    #[msg("Migration has already been prepared")]
+    MigrationAlreadyPrepared,
+    #[msg("Migration has not been prepared")]
+    MigrationNotPrepared,
+    #[msg("Trading is locked while the curve migrates")]
+    TradingLocked,
+    #[msg("Invalid vesting schedule, cliff must be within a non zero duration")]
+    InvalidVestingSchedule,
+    #[msg("Vesting accounts are required to lock the dev-buy")]
+    MissingVestingAccounts,
+    #[msg("Nothing vested to claim yet")]
+    NothingToClaim,
+    #[msg("Token name is empty")]
+    EmptyName,
+    #[msg("Token name exceeds the configured max length")]
+    NameTooLong,
+    #[msg("Token name contains characters that are not allowed")]
+    InvalidNameCharacters,
+    #[msg("Token symbol is empty")]
+    EmptySymbol,
+    #[msg("Token symbol exceeds the configured max length")]
+    SymbolTooLong,
+    #[msg("Token symbol contains characters that are not allowed")]
+    InvalidSymbolCharacters,
+    #[msg("Token uri exceeds the configured max length")]
+    UriTooLong,
+    #[msg("Token uri doesn't use an allowed scheme")]
+    InvalidUriScheme,
+    #[msg("Symbol is already used by another token")]
+    SymbolTaken,
+    #[msg("Symbol is reserved for another creator")]
+    SymbolReserved,
+    #[msg("Invalid metadata policy")]
+    InvalidMetadataPolicy,
+    #[msg("Only the token creator can do this")]
+    UnauthorizedCreator,
+    #[msg("Metadata is locked once the migration started")]
+    MetadataLocked,
+    #[msg("Token has no metadata account")]
+    MissingTokenMetadata,
+    #[msg("Token profile field exceeds its max length")]
+    ProfileFieldTooLong,
+    #[msg("Token profile links must be https urls")]
+    InvalidProfileUrl,
+    #[msg("Token profile account is required to set a profile")]
+    MissingProfileAccount,
+    #[msg("Token decimals exceed the max allowed")]
+    InvalidDecimals,
+    #[msg("Total supply is outside the allowed bounds")]
+    InvalidTotalSupply,
+    #[msg("Min total supply must be non zero and not above the max")]
+    InvalidSupplyBounds,
+    #[msg("Verified listing fee can't exceed the regular one")]
+    InvalidListingFee,
+    #[msg("Creator launched too many tokens in the rate limit window")]
+    LaunchRateLimited,
+    #[msg("Invalid launch rate limit")]
+    InvalidRateLimit,
+    #[msg("Listing fee platform share can't exceed 100%")]
+    InvalidFeeSplit,
+    #[msg("Trading hasn't started yet")]
+    TradingNotStarted,
+    #[msg("Trading start is too far in the future")]
+    InvalidTradingStart,
+    #[msg("Allowlist phase must end after the trading start, within the max duration")]
+    InvalidAllowlist,
+    #[msg("Buyer is not on the allowlist")]
+    NotAllowlisted,
+    #[msg("Allowlist proof and purchase account are required during the allowlist phase")]
+    MissingAllowlistProof,
+    #[msg("Purchase exceeds the buyer allowlist allocation")]
+    AllowlistAllocationExceeded,
+    #[msg("Graduation deadline must be after the trading start and allowlist phase, within the max delay")]
+    InvalidGraduationDeadline,
+    #[msg("Graduation deadline has passed")]
+    GraduationDeadlinePassed,
+    #[msg("Graduation deadline has not passed yet")]
+    GraduationDeadlineNotReached,
+    #[msg("Refunds are not open for this token")]
+    RefundsNotOpen,
+    #[msg("Amount is too small to redeem any lamports")]
+    NothingToRefund,
+    #[msg("Refund redemption is not open yet")]
+    RedemptionNotOpen,
+    #[msg("Unwind delay is above the max")]
+    InvalidUnwindDelay,
+    #[msg("Allowlist purchase account is only accepted during the allowlist phase")]
+    AllowlistPhaseOver,
+    #[msg("Pool account doesn't match the address derived for the venue")]
+    InvalidPoolAddress,
+    #[msg("Account already uses the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Account is not in a known legacy layout")]
+    UnknownAccountLayout,
+    #[msg("Purchase would raise more SOL than the unsold supply pairs with at the curve price")]
+    PoolCapacityExceeded,
+    #[msg("Token verifications need the token state of a launched mint")]
+    UnknownVerificationSubject,
+    #[msg("Sells don't take an allowlist purchase account")]
+    UnexpectedAllowlistPurchase,
+    #[msg("Locked dev-buy is forfeited once the curve goes into refund")]
+    VestingForfeited,
 }
 
 #[error_code]
@@ -0,0 +1,213 @@
+use anchor_lang::{prelude::*, solana_program::program_pack::Pack};
+use anchor_spl::{
+    token::{spl_token, Token},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use raydium_cpmm_cpi::states::AmmConfig;
+
+use crate::{
+    error::NottyTerminalError, GlobalState, MigrationAmounts, MigrationStage, MigrationVenue,
+    TokenState,
+};
+
+/// Read only dry run of the next migration step. Runs the same validations as
+/// `prepare_migration` / `execute_migration` without any CPI and fails with the error
+/// the real step would raise, otherwise returns a `MigrationReport` as return data.
+#[derive(Accounts)]
+pub struct CheckMigration<'info> {
+    /// Address that would send the migration, checked for fee and rent
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        constraint = token_mint.key() == token_state.mint.key() @NottyTerminalError::WrongMint,
+        constraint = token_mint.key() != wsol_mint.key() @NottyTerminalError::InvalidTokenOrdering,
+        mint::token_program = launch_token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            b"token_state", token_mint.key().as_ref()
+        ],
+        bump = token_state.bump,
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    #[account(
+        constraint = token_vault.owner == token_state.key(),
+        constraint = token_vault.mint == token_mint.key() @NottyTerminalError::WrongMint,
+        token::token_program = launch_token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"sol_vault", token_vault.key().as_ref()],
+        bump = token_state.sol_vault_bump,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        address = spl_token::native_mint::ID,
+        mint::token_program = token_program,
+    )]
+    pub wsol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Curve WSOL account, None until `prepare_migration` created it
+    #[account(
+        associated_token::mint = wsol_mint,
+        associated_token::authority = token_state,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_wsol_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Raydium CPMM config the pool would use, required for that venue only
+    pub amm_config: Option<Box<Account<'info, AmmConfig>>>,
+
+    /// Program of the WSOL side
+    pub token_program: Program<'info, Token>,
+    /// Program of the launch token, spl token or token program 2022
+    pub launch_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CheckMigration<'info> {
+    pub fn handle_check_migration(&self) -> Result<MigrationReport> {
+        let stage = self.token_state.migration_stage;
+        let venue = self.token_state.venue;
+
+        let (amounts, migration_fee, rent_required) = match stage {
+            MigrationStage::Trading => {
+                let amounts = self.token_state.validate_migration_prepare(
+                    &self.global_state,
+                    &self.launch_token_program.key(),
+                    Clock::get()?.unix_timestamp,
+                )?;
+
+                require!(
+                    self.token_vault.amount >= amounts.token_amount + amounts.tokens_burned,
+                    NottyTerminalError::InsufficientVaultBalance
+                );
+                require!(
+                    self.sol_vault.lamports() >= amounts.sol_amount,
+                    NottyTerminalError::VaultInsufficientSol
+                );
+
+                // the curve WSOL account is created on prepare if it doesn't exist yet
+                let rent_required = match self.vault_wsol_account {
+                    Some(_) => 0,
+                    None => Rent::get()?.minimum_balance(spl_token::state::Account::LEN),
+                };
+
+                (
+                    amounts,
+                    self.global_state.migration_fee_lamport,
+                    rent_required,
+                )
+            }
+            _ => {
+                self.token_state
+                    .validate_migration_execute(venue, &self.global_state)?;
+
+                let amounts = self.token_state.migration_amounts;
+                let vault_wsol_account = self
+                    .vault_wsol_account
+                    .as_ref()
+                    .ok_or(NottyTerminalError::InsufficientVaultBalance)?;
+
+                require!(
+                    self.token_vault.amount >= amounts.token_amount
+                        && vault_wsol_account.amount >= amounts.sol_amount,
+                    NottyTerminalError::InsufficientVaultBalance
+                );
+
+                (amounts, 0, 0)
+            }
+        };
+
+        require!(
+            self.signer.lamports() >= migration_fee + rent_required,
+            NottyTerminalError::InsufficientFunds
+        );
+
+        self.validate_venue(venue)?;
+
+        let token_first = self.token_mint.key() < self.wsol_mint.key();
+        let (token_0_mint, token_1_mint, init_amount_0, init_amount_1) = if token_first {
+            (
+                self.token_mint.key(),
+                self.wsol_mint.key(),
+                amounts.token_amount,
+                amounts.sol_amount,
+            )
+        } else {
+            (
+                self.wsol_mint.key(),
+                self.token_mint.key(),
+                amounts.sol_amount,
+                amounts.token_amount,
+            )
+        };
+
+        Ok(MigrationReport {
+            stage,
+            venue,
+            sol_raised: self.token_state.sol_raised,
+            target_sol: self.token_state.target_sol,
+            migration_fee,
+            rent_required,
+            amounts,
+            token_0_mint,
+            token_1_mint,
+            init_amount_0,
+            init_amount_1,
+        })
+    }
+
+    /// Venue specific checks the adapters run before their CPI
+    fn validate_venue(&self, venue: MigrationVenue) -> Result<()> {
+        match venue {
+            MigrationVenue::RaydiumCpmm => {
+                let amm_config = self
+                    .amm_config
+                    .as_ref()
+                    .ok_or(NottyTerminalError::InvalidAmmConfig)?;
+                require!(
+                    self.global_state.is_amm_config_allowed(&amm_config.key()),
+                    NottyTerminalError::InvalidAmmConfig
+                );
+            }
+            MigrationVenue::RaydiumAmmV4 | MigrationVenue::OrcaWhirlpool => {
+                require_keys_eq!(
+                    self.launch_token_program.key(),
+                    Token::id(),
+                    NottyTerminalError::UnsupportedTokenProgram
+                );
+            }
+            MigrationVenue::MeteoraDamm => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Returned by `check_migration`, what the next migration step would do
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MigrationReport {
+    pub stage: MigrationStage, // Trading -> prepare_migration is next, Prepared -> execute_migration
+    pub venue: MigrationVenue,
+    /// the curve can be prepared once `sol_raised >= target_sol`
+    pub sol_raised: u64,
+    pub target_sol: u64,
+    pub migration_fee: u64,        // lamports the signer pays on prepare
+    pub rent_required: u64,        // lamports the signer pays for accounts created on prepare
+    pub amounts: MigrationAmounts, // price_per_million is the pool opening price
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub init_amount_0: u64,
+    pub init_amount_1: u64,
+}
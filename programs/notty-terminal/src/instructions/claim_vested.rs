@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::NottyTerminalError, CreatorVesting, MigrationStage, TokenState};
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        constraint = token_mint.key() == token_state.mint.key() @NottyTerminalError::WrongMint,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"token_state", token_mint.key().as_ref()
+        ],
+        bump = token_state.bump,
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vesting", token_mint.key().as_ref()],
+        bump = creator_vesting.bump,
+        has_one = creator @NottyTerminalError::WrongCreator,
+    )]
+    pub creator_vesting: Box<Account<'info, CreatorVesting>>,
+
+    #[account(
+        mut,
+        associated_token::authority = creator_vesting,
+        associated_token::token_program = token_program,
+        associated_token::mint = token_mint,
+    )]
+    pub vesting_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+        associated_token::mint = token_mint,
+        payer = creator
+    )]
+    pub creator_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimVested<'info> {
+    pub fn handle_claim_vested(&mut self) -> Result<()> {
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        // the locked dev-buy takes no share of the refund pool, see `open_refunds`
+        require!(
+            self.token_state.migration_stage != MigrationStage::Refunding,
+            NottyTerminalError::VestingForfeited
+        );
+
+        let amount = self.creator_vesting.claimable_amount(timestamp);
+        require!(amount > 0, NottyTerminalError::NothingToClaim);
+
+        let mint = self.token_mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"creator_vesting",
+            mint.as_ref(),
+            &[self.creator_vesting.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    authority: self.creator_vesting.to_account_info(),
+                    from: self.vesting_vault.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.creator_ata.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.token_mint.decimals,
+        )?;
+
+        self.creator_vesting.claimed_amount = self
+            .creator_vesting
+            .claimed_amount
+            .checked_add(amount)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+
+        self.token_state.dev_buy_locked_amount = self.creator_vesting.locked_amount();
+
+        emit!(VestedClaimed {
+            mint,
+            creator: self.creator.key(),
+            amount,
+            claimed_amount: self.creator_vesting.claimed_amount,
+            locked_amount: self.creator_vesting.locked_amount(),
+            timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct VestedClaimed {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub claimed_amount: u64, // total released so far
+    pub locked_amount: u64,  // still held by the vesting account
+    pub timestamp: i64,
+}
@@ -0,0 +1,116 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    token_2022_extensions::{
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::TokenMetadata, token_metadata_initialize,
+        TokenMetadataInitialize,
+    },
+    token_interface::{Mint, TokenInterface},
+};
+
+use super::{create_token::CreateTokenArgs, token_launch::TokenLaunch};
+use crate::error::NottyTerminalError;
+
+/// Launches a Token-2022 mint carrying its own metadata through the MetadataPointer and
+/// TokenMetadata extensions, no Metaplex account involved. Same curve as `CreateToken`.
+/// The mint carries no other extension, Raydium CPMM and Meteora DAMM v2 reject pool
+/// mints with a transfer hook and Raydium AMM v4 / Orca Whirlpools take spl token mints
+/// only, so Token-2022 launches graduate to the first two.
+#[derive(Accounts)]
+#[instruction(args: CreateTokenArgs)]
+pub struct CreateToken2022<'info> {
+    pub launch: TokenLaunch<'info>,
+
+    #[account(
+        init,
+        constraint = creator_mint.key() == launch.creator_mint.key() @NottyTerminalError::WrongMint,
+        mint::authority = launch.token_state,
+        mint::decimals = args.mint_decimals(),
+        mint::token_program = token_program,
+        mint::freeze_authority = launch.token_state,
+        extensions::metadata_pointer::authority = launch.token_state,
+        extensions::metadata_pointer::metadata_address = creator_mint,
+        payer = launch.creator
+    )]
+    pub creator_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    /// Token program 2022, the only one supporting the metadata extensions
+    #[account(
+        address = anchor_spl::token_2022::ID,
+        constraint = token_program.key() == launch.token_program.key() @NottyTerminalError::UnsupportedTokenProgram,
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CreateToken2022<'info> {
+    pub fn handle_create_token_2022(
+        &mut self,
+        args: CreateTokenArgs,
+        bumps: &CreateToken2022Bumps,
+    ) -> Result<()> {
+        self.launch.register(&args, &bumps.launch)?;
+
+        // the metadata lives in the mint itself, top it up for the TLV entry first
+        let token_metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey(self.launch.token_state.key()),
+            mint: self.creator_mint.key(),
+            name: args.name.clone(),
+            symbol: args.token_symbol.clone(),
+            uri: args.token_uri.clone(),
+            additional_metadata: vec![],
+        };
+
+        let metadata_len = token_metadata
+            .tlv_size_of()
+            .map_err(|_| NottyTerminalError::NumericalOverflow)?;
+        let mint_info = self.creator_mint.to_account_info();
+        let required_lamports = Rent::get()?
+            .minimum_balance(mint_info.data_len() + metadata_len)
+            .saturating_sub(mint_info.lamports());
+
+        if required_lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.launch.creator.to_account_info(),
+                        to: mint_info,
+                    },
+                ),
+                required_lamports,
+            )?;
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"token_state",
+            &self.creator_mint.key().to_bytes(),
+            &[bumps.launch.token_state],
+        ]];
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TokenMetadataInitialize {
+                    program_id: self.token_program.to_account_info(),
+                    metadata: self.creator_mint.to_account_info(),
+                    update_authority: self.launch.token_state.to_account_info(),
+                    mint_authority: self.launch.token_state.to_account_info(),
+                    mint: self.creator_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            args.name.clone(),
+            args.token_symbol.clone(),
+            args.token_uri.clone(),
+        )?;
+
+        // launch last, it renounces the mint authority the metadata init needs
+        self.launch.launch(&args, &bumps.launch)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::Metadata,
+    token_interface::{Mint, TokenInterface},
+};
+
+use super::{
+    create_token::CreateTokenArgs, token_launch::TokenLaunch, token_metadata::LaunchMetadata,
+};
+use crate::{error::NottyTerminalError, CreatorLaunches};
+
+/// Same launch as `CreateToken`, but the mint is a PDA of the creator and their launch
+/// count instead of a fresh keypair. The address is known ahead of time and the creator
+/// is the only signer.
+#[derive(Accounts)]
+#[instruction(args: CreateTokenArgs)]
+pub struct CreateTokenPda<'info> {
+    pub launch: TokenLaunch<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"creator_launches", launch.creator.key().as_ref()],
+        bump,
+        space = 8 + CreatorLaunches::INIT_SPACE,
+        payer = launch.creator
+    )]
+    pub creator_launches: Box<Account<'info, CreatorLaunches>>,
+
+    /// PDA of (creator, creator_launches.launch_count), known before the launch
+    #[account(
+        init,
+        seeds = [
+            b"launch_mint",
+            launch.creator.key().as_ref(),
+            &creator_launches.launch_count.to_le_bytes(),
+        ],
+        bump,
+        constraint = creator_mint.key() == launch.creator_mint.key() @NottyTerminalError::WrongMint,
+        mint::authority = launch.token_state,
+        mint::decimals = args.mint_decimals(),
+        mint::token_program = token_program,
+        mint::freeze_authority = launch.token_state,
+        payer = launch.creator
+    )]
+    pub creator_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            creator_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    /// CHECK: Metaplex creates this account
+    pub metadata_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    #[account(address = launch.token_program.key())]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> CreateTokenPda<'info> {
+    pub fn handle_create_token_pda(
+        &mut self,
+        args: CreateTokenArgs,
+        bumps: &CreateTokenPdaBumps,
+    ) -> Result<()> {
+        self.launch.register(&args, &bumps.launch)?;
+
+        // create token metadata while token_state still holds the mint authority
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"token_state",
+            &self.creator_mint.key().to_bytes(),
+            &[bumps.launch.token_state],
+        ]];
+
+        LaunchMetadata {
+            mint: self.creator_mint.to_account_info(),
+            token_state: self.launch.token_state.to_account_info(),
+            launch_token_program: self.token_program.to_account_info(),
+            metadata_account: self.metadata_account.to_account_info(),
+            token_metadata_program: self.token_metadata_program.to_account_info(),
+            signer_seeds,
+        }
+        .create_metaplex(
+            &args,
+            self.launch.creator.to_account_info(),
+            self.system_program.to_account_info(),
+            self.rent.to_account_info(),
+        )?;
+
+        self.launch.launch(&args, &bumps.launch)?;
+
+        // the next launch of this creator derives a new mint
+        let nonce = self.creator_launches.launch_count;
+        self.creator_launches.bump = bumps.creator_launches;
+        self.creator_launches.creator = self.launch.creator.key();
+        self.creator_launches.launch_count = nonce
+            .checked_add(1)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+
+        emit!(PdaMintLaunched {
+            mint: self.creator_mint.key(),
+            creator: self.launch.creator.key(),
+            nonce,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct PdaMintLaunched {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub nonce: u64,
+}
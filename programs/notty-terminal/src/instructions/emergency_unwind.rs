@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{close_account, spl_token, CloseAccount, Token},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{error::NottyTerminalError, GlobalState, MigrationStage, RefundReason, TokenState};
+
+/// Admin shutdown of an exploitable or stuck curve. Trading and migration stop right away,
+/// holders can redeem a pro-rata share of the sol vault once `GlobalState::unwind_delay`
+/// has passed
+#[derive(Accounts)]
+pub struct EmergencyUnwind<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = admin @NottyTerminalError::UnauthorizedAdmin
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        constraint = token_mint.key() == token_state.mint.key() @NottyTerminalError::WrongMint,
+        mint::token_program = launch_token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"token_state", token_mint.key().as_ref()
+        ],
+        bump = token_state.bump,
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    #[account(
+        constraint = token_vault.owner == token_state.key(),
+        constraint = token_vault.mint == token_mint.key() @NottyTerminalError::WrongMint,
+        token::token_program = launch_token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", token_vault.key().as_ref()],
+        bump = token_state.sol_vault_bump,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        address = spl_token::native_mint::ID,
+        mint::token_program = token_program,
+    )]
+    pub wsol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Curve WSOL account, required to unwind a prepared migration so its SOL is refunded too
+    #[account(
+        mut,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = token_state,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_wsol_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Program of the WSOL side
+    pub token_program: Program<'info, Token>,
+    /// Program of the launch token, spl token or token program 2022
+    pub launch_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> EmergencyUnwind<'info> {
+    pub fn handle_emergency_unwind(&mut self, args: EmergencyUnwindArgs) -> Result<()> {
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        match self.token_state.migration_stage {
+            MigrationStage::Trading => {}
+            // the raised SOL is wrapped, bring it back to the sol vault first
+            MigrationStage::Prepared => self.unwrap_sol()?,
+            _ => return err!(NottyTerminalError::TradingLocked),
+        }
+
+        // the vault keeps its rent exemption, everything above goes back to the holders
+        let lamports = self
+            .sol_vault
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        let opens_at = timestamp
+            .checked_add(self.global_state.unwind_delay)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        let refund_pool =
+            self.token_state
+                .open_refunds(RefundReason::EmergencyUnwind, lamports, opens_at);
+
+        emit!(EmergencyUnwindStarted {
+            mint: self.token_mint.key(),
+            admin: self.admin.key(),
+            reason_code: args.reason_code,
+            sol_raised: refund_pool.sol_raised,
+            tokens_sold: refund_pool.tokens_sold,
+            lamports: refund_pool.lamports,
+            redemption_opens_at: opens_at,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn unwrap_sol(&mut self) -> Result<()> {
+        let vault_wsol_account = self
+            .vault_wsol_account
+            .as_ref()
+            .ok_or(NottyTerminalError::InsufficientVaultBalance)?;
+
+        let token_state_seeds: &[&[&[u8]]] = &[&[
+            b"token_state",
+            self.token_state.mint.as_ref(),
+            &[self.token_state.bump],
+        ]];
+
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: vault_wsol_account.to_account_info(),
+                destination: self.sol_vault.to_account_info(),
+                authority: self.token_state.to_account_info(),
+            },
+            token_state_seeds,
+        ))
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct EmergencyUnwindArgs {
+    pub reason_code: u16, // off-chain incident reference, only emitted
+}
+
+#[event]
+pub struct EmergencyUnwindStarted {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub reason_code: u16,
+    pub sol_raised: u64,
+    pub tokens_sold: u64,
+    pub lamports: u64,
+    pub redemption_opens_at: i64,
+    pub timestamp: i64,
+}
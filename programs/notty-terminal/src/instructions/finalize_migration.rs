@@ -0,0 +1,196 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    token::{spl_token, Token},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{error::NottyTerminalError, GlobalState, TokenState};
+
+/// Closes what is left of a curve once its liquidity sits in a pool. Can be cranked by anyone,
+/// every lamport goes to `global_state.rent_receiver`. The pool took all the raised SOL, what
+/// remains is account rent and rounding dust.
+#[derive(Accounts)]
+pub struct FinalizeMigration<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        address = global_state.rent_receiver @NottyTerminalError::InvalidRentReceiver
+    )]
+    pub rent_receiver: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == token_state.mint.key() @NottyTerminalError::WrongMint,
+        mint::token_program = launch_token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"token_state", token_mint.key().as_ref()
+        ],
+        bump = token_state.bump,
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    #[account(
+        mut,
+        constraint = token_vault.owner == token_state.key(),
+        constraint = token_vault.mint == token_mint.key() @NottyTerminalError::WrongMint,
+        token::token_program = launch_token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", token_vault.key().as_ref()],
+        bump = token_state.sol_vault_bump,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(address = spl_token::native_mint::ID)]
+    pub wsol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = token_state,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_wsol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    /// Program of the launch token, spl token or token program 2022
+    pub launch_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FinalizeMigration<'info> {
+    pub fn handle_finalize_migration(&mut self) -> Result<()> {
+        require!(self.token_state.migrated, NottyTerminalError::NotGraduated);
+        require!(
+            !self.token_state.finalized,
+            NottyTerminalError::AlreadyFinalized
+        );
+
+        let rent_receiver_balance = self.rent_receiver.lamports();
+
+        let token_state_seeds: &[&[&[u8]]] = &[&[
+            b"token_state",
+            self.token_state.mint.as_ref(),
+            &[self.token_state.bump],
+        ]];
+
+        // burn unsold dust left in the curve
+        let tokens_burned = self.token_vault.amount;
+        if tokens_burned > 0 {
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    self.launch_token_program.to_account_info(),
+                    token_interface::Burn {
+                        mint: self.token_mint.to_account_info(),
+                        from: self.token_vault.to_account_info(),
+                        authority: self.token_state.to_account_info(),
+                    },
+                    token_state_seeds,
+                ),
+                tokens_burned,
+            )?;
+        }
+
+        self.close_token_vault(token_state_seeds)?;
+
+        // closing a native account unwraps whatever WSOL the pool didn't take
+        let wsol_swept = self.vault_wsol_account.amount;
+        token_interface::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: self.vault_wsol_account.to_account_info(),
+                destination: self.sol_vault.to_account_info(),
+                authority: self.token_state.to_account_info(),
+            },
+            token_state_seeds,
+        ))?;
+
+        self.sweep_sol_vault()?;
+
+        self.token_state.finalized = true;
+
+        let lamports_reclaimed = self
+            .rent_receiver
+            .lamports()
+            .checked_sub(rent_receiver_balance)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+
+        emit!(MigrationFinalized {
+            mint: self.token_state.mint,
+            tokens_burned,
+            wsol_swept,
+            lamports_reclaimed,
+            rent_receiver: self.rent_receiver.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn close_token_vault(&self, token_state_seeds: &[&[&[u8]]]) -> Result<()> {
+        token_interface::close_account(CpiContext::new_with_signer(
+            self.launch_token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: self.token_vault.to_account_info(),
+                destination: self.rent_receiver.to_account_info(),
+                authority: self.token_state.to_account_info(),
+            },
+            token_state_seeds,
+        ))
+    }
+
+    /// Empties the sol vault into the rent receiver, closing it
+    fn sweep_sol_vault(&self) -> Result<()> {
+        let lamports = self.sol_vault.lamports();
+        if lamports == 0 {
+            return Ok(());
+        }
+
+        let token_vault = self.token_vault.key();
+        let sol_vault_seeds: &[&[&[u8]]] = &[&[
+            b"sol_vault",
+            token_vault.as_ref(),
+            &[self.token_state.sol_vault_bump],
+        ]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.sol_vault.to_account_info(),
+                    to: self.rent_receiver.to_account_info(),
+                },
+                sol_vault_seeds,
+            ),
+            lamports,
+        )
+    }
+}
+
+#[event]
+pub struct MigrationFinalized {
+    pub mint: Pubkey,
+    pub tokens_burned: u64,
+    pub wsol_swept: u64,         // unwrapped into the sol vault
+    pub lamports_reclaimed: u64, // account rent and swept lamports
+    pub rent_receiver: Pubkey,
+    pub timestamp: i64,
+}
@@ -7,8 +7,10 @@ pub struct InitializeGlobalState<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
 
+    /// Created once, calling `initialize` again fails instead of handing the platform
+    /// to a new admin
     #[account(
-        init_if_needed,
+        init,
         payer = admin,
         seeds = [b"global_state"],
         space = 8 + GlobalState::INIT_SPACE,
@@ -42,19 +44,13 @@ impl<'info> InitializeGlobalState<'info> {
             NottyTerminalError::InvalidMigrationFee
         );
         
-        self.global_state.set_inner(GlobalState {
-            admin: self.admin.key(),
-            vault: self.vault.key(),
-            vault_bump: bumps.vault,
-            bump: bumps.global_state,
-            listing_fee_lamport: args.listing_fee_lamport,
-            trading_fee_bps: args.trading_fee_bps,
-            migration_fee_lamport: args.migration_fee_lamport,
-            total_tokens_created: 0,
-            total_fees_collected: 0,
-            total_trading_volume: 0,
-            total_migrations: 0,
-        });
+        self.global_state.set_inner(GlobalState::new(
+            self.admin.key(),
+            self.vault.key(),
+            bumps.vault,
+            bumps.global_state,
+            &args,
+        ));
 
         Ok(())
     }
@@ -66,5 +62,6 @@ pub struct InitializeArgs {
     pub listing_fee_lamport: u64,      // 50_000_000 (0.05 SOL)
     pub trading_fee_bps: u16,          // 150 (1.5%)
     pub migration_fee_lamport: u64,    // 150_000_000 (0.15 SOL)
+    pub amm_config: Pubkey,            // default Raydium CPMM config (25 bps)
             
 }
\ No newline at end of file
@@ -1,41 +1,24 @@
-use anchor_lang::{
-    prelude::*,
-    system_program::{transfer, Transfer},
-};
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{self, Token},
-    token_interface::{Mint, TokenAccount, TokenInterface},
-};
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::get_associated_token_address, token_interface::TokenAccount};
 use raydium_cpmm_cpi::{
     cpi,
     program::RaydiumCpmm,
     states::{AmmConfig, OBSERVATION_SEED, POOL_LP_MINT_SEED, POOL_SEED, POOL_VAULT_SEED},
 };
 
-pub use crate::{error::NottyTerminalError, integer_sqrt, GlobalState, TokenState};
-pub const INITIAL_MCAP_SOL: u64 = 50;
-pub const MIGRATION_MCAP_SOL: u64 = 450;
-pub const TOTAL_SUPPLY: u64 = 1_000_000_000; // 1B tokens
-pub const MIGRATION_THRESHOLD_PCT: u64 = 86; // Migration at 86% sold
-
-use std::cmp::min;
+use super::migration_common::*;
+pub use crate::{error::NottyTerminalError, GlobalState, MigrationVenue, TokenState};
 
 #[derive(Accounts)]
-#[instruction(param: LaunchParam)]
 pub struct Launch<'info> {
+    pub common: MigrationCommon<'info>,
+
     pub cp_swap_program: Program<'info, RaydiumCpmm>,
-    /// Address paying to create the pool. Can be anyone
 
+    /// Which config the pool belongs to, must be whitelisted by the admin.
     #[account(
-        constraint = token_state.creator.key() == creator.key() @NottyTerminalError::WrongCreator
-   )]
-    pub creator: SystemAccount<'info>,
-
-    #[account(mut)]
-    pub signer: Signer<'info>,
-
-    /// Which config the pool belongs to.
+        constraint = common.global_state.is_amm_config_allowed(&amm_config.key()) @NottyTerminalError::InvalidAmmConfig
+    )]
     pub amm_config: Box<Account<'info, AmmConfig>>,
 
     /// CHECK: pool vault and lp mint authority
@@ -48,45 +31,20 @@ pub struct Launch<'info> {
     )]
     pub authority: UncheckedAccount<'info>,
 
-    #[account(
-        mut,
-        constraint = platform_sol_vault.key() == global_state.vault.key() @NottyTerminalError::WrongVault
-    )]
-    pub platform_sol_vault: SystemAccount<'info>,
-
-    #[account(
-        seeds = [b"global_state"],
-        bump = global_state.bump
-    )]
-    pub global_state: Account<'info, GlobalState>,
-
     /// CHECK: Initialize an account to store the pool state, init by cp-swap
     #[account(
         mut,
         seeds = [
             POOL_SEED.as_bytes(),
             amm_config.key().as_ref(),
-            token_0_mint.key().as_ref(),
-            token_1_mint.key().as_ref(),
+            common.sorted_mint_keys().0.as_ref(),
+            common.sorted_mint_keys().1.as_ref(),
         ],
         seeds::program = cp_swap_program.key(),
         bump,
     )]
     pub pool_state: UncheckedAccount<'info>,
 
-    /// Token_0 mint, the key must smaller then token_1 mint.
-    #[account(
-        constraint = token_0_mint.key() < token_1_mint.key(),
-        mint::token_program = token_0_program,
-    )]
-    pub token_0_mint: Box<InterfaceAccount<'info, Mint>>,
-
-    /// Token_1 mint, the key must grater then token_0 mint.
-    #[account(
-        mint::token_program = token_1_program,
-    )]
-    pub token_1_mint: Box<InterfaceAccount<'info, Mint>>,
-
     /// CHECK: pool lp mint, init by cp-swap
     #[account(
         mut,
@@ -99,44 +57,28 @@ pub struct Launch<'info> {
     )]
     pub lp_mint: UncheckedAccount<'info>,
 
-    /// payer token0 account
+    /// CHECK: pool creator lp ATA, init by cp-swap and closed once the LP tokens are
+    /// handed to the creator
     #[account(
         mut,
-        token::mint = token_0_mint,
-        token::authority = creator,
+        address = get_associated_token_address(&common.pool_creator.key(), &lp_mint.key()),
     )]
-    pub creator_token_0: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub pool_creator_lp_token: UncheckedAccount<'info>,
 
-    /// creator token1 account
+    /// CHECK: creator lp ATA, receives the LP tokens. Created once the lp mint exists
     #[account(
         mut,
-        token::mint = token_1_mint,
-        token::authority = creator,
+        address = get_associated_token_address(&common.creator.key(), &lp_mint.key()),
     )]
-    pub creator_token_1: Box<InterfaceAccount<'info, TokenAccount>>,
-
-    /// CHECK: creator lp ATA token account, init by cp-swap
-    #[account(mut)]
     pub creator_lp_token: UncheckedAccount<'info>,
 
-    // using tokenMint because token order could be rotated from client
-    #[account(
-        mut,
-        seeds = [
-            b"token_state", param.token_mint.key().as_ref()
-        ],
-        bump = token_state.bump,
-        constraint = param.token_mint.key() == token_state.mint.key() @NottyTerminalError::WrongMint
-    )]
-    pub token_state: Box<Account<'info, TokenState>>,
-
     /// CHECK: Token_0 vault for the pool, init by cp-swap
     #[account(
         mut,
         seeds = [
             POOL_VAULT_SEED.as_bytes(),
             pool_state.key().as_ref(),
-            token_0_mint.key().as_ref()
+            common.sorted_mint_keys().0.as_ref()
         ],
         seeds::program = cp_swap_program.key(),
         bump,
@@ -149,7 +91,7 @@ pub struct Launch<'info> {
         seeds = [
             POOL_VAULT_SEED.as_bytes(),
             pool_state.key().as_ref(),
-            token_1_mint.key().as_ref()
+            common.sorted_mint_keys().1.as_ref()
         ],
         seeds::program = cp_swap_program.key(),
         bump,
@@ -174,258 +116,90 @@ pub struct Launch<'info> {
         bump,
     )]
     pub observation_state: UncheckedAccount<'info>,
+}
 
-    #[account(
-        init_if_needed,
-        payer = signer,
-        associated_token::mint = wsol_mint,
-        associated_token::authority = token_state)]
-    pub vault_wsol_account: Box<InterfaceAccount<'info, TokenAccount>>,
-
-    pub wsol_mint: Box<InterfaceAccount<'info, Mint>>,
+impl<'info> MigrationAdapter<'info> for Launch<'info> {
+    const VENUE: MigrationVenue = MigrationVenue::RaydiumCpmm;
 
-    #[account(
-        mut,
-        constraint = token_vault.owner == token_state.key(),
-    )]
-    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    // 0.15 SOL create pool fee and ~0.04 SOL of pool account rent
+    const POOL_CREATION_BUDGET: u64 = 500_000_000;
 
-    #[account(
-        mut,
-        seeds = [b"sol_vault", token_vault.key().as_ref()],
-        bump = token_state.sol_vault_bump,
-    )]
-    pub sol_vault: SystemAccount<'info>,
+    type Params = LaunchParam;
 
-    /// Program to create mint account and mint tokens
-    pub token_program: Program<'info, Token>,
-    /// Spl token program or token program 2022
-    pub token_0_program: Interface<'info, TokenInterface>,
-    /// Spl token program or token program 2022
-    pub token_1_program: Interface<'info, TokenInterface>,
-    /// Program to create an ATA for receiving position NFT
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    /// To create a new program account
-    pub system_program: Program<'info, System>,
-    /// Sysvar for program account
-    pub rent: Sysvar<'info, Rent>,
-}
+    fn common(&mut self) -> &mut MigrationCommon<'info> {
+        &mut self.common
+    }
 
-impl<'info> Launch<'info> {
-    pub fn handle_launch(
+    fn create_pool(
         &mut self,
-        init_amount_0: u64,
-        init_amount_1: u64,
-        open_time: u64,
-    ) -> Result<()> {
-        require!(
-            !self.token_state.migrated,
-            NottyTerminalError::AlreadyMigrated
-        );
-
-        let current_mcap = self.calculate_current_market_cap()?;
-        require!(
-            current_mcap >= 450_000_000_000, // 450 SOL in lamports
-            NottyTerminalError::TargetNotReached
-        );
-
-        // collect Notty Migration Fee
-        let migration_fee = self.global_state.migration_fee_lamport;
-        transfer(
-            CpiContext::new(
-                self.system_program.to_account_info(),
-                Transfer {
-                    from: self.signer.to_account_info(),
-                    to: self.platform_sol_vault.to_account_info(),
-                },
-            ),
-            migration_fee,
-        )?;
-
-        // Update global metrics
-        self.global_state.total_migrations = self
-            .global_state
-            .total_migrations
-            .checked_add(1)
-            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        token_amount: u64,
+        sol_amount: u64,
+        params: LaunchParam,
+        bumps: &MigrationCommonBumps,
+    ) -> Result<Pubkey> {
+        let open_time = match params.time {
+            Some(value) => value as u64,
+            None => Clock::get()?.unix_timestamp as u64,
+        };
 
-        // Step 1: Wrap SOL
-        self.wrap_sol()?;
-        // Step 2: Transfer tokens to creator accounts for Raydium
-        self.prepare_liquidity(init_amount_0, init_amount_1)?;
+        // Raydium wants token_0 < token_1, the launch token can land on either side of WSOL
+        let (token_0_mint, token_1_mint) = self.common.sorted_mints();
+        let (creator_token_0, creator_token_1) = self.common.sorted_pool_creator_accounts();
+        let (token_0_program, token_1_program) = self.common.sorted_token_programs();
+        let (init_amount_0, init_amount_1) = self.common.sorted_amounts(token_amount, sol_amount);
 
         let cpi_accounts = cpi::accounts::Initialize {
-            creator: self.creator.to_account_info(),
+            creator: self.common.pool_creator.to_account_info(),
             amm_config: self.amm_config.to_account_info(),
             authority: self.authority.to_account_info(),
             pool_state: self.pool_state.to_account_info(),
-            token_0_mint: self.token_0_mint.to_account_info(),
-            token_1_mint: self.token_1_mint.to_account_info(),
+            token_0_mint,
+            token_1_mint,
             lp_mint: self.lp_mint.to_account_info(),
-            creator_token_0: self.creator_token_0.to_account_info(),
-            creator_token_1: self.creator_token_1.to_account_info(),
-            creator_lp_token: self.creator_lp_token.to_account_info(),
+            creator_token_0,
+            creator_token_1,
+            creator_lp_token: self.pool_creator_lp_token.to_account_info(),
             token_0_vault: self.token_0_vault.to_account_info(),
             token_1_vault: self.token_1_vault.to_account_info(),
             create_pool_fee: self.create_pool_fee.to_account_info(),
             observation_state: self.observation_state.to_account_info(),
-            token_program: self.token_program.to_account_info(),
-            token_0_program: self.token_0_program.to_account_info(),
-            token_1_program: self.token_1_program.to_account_info(),
-            associated_token_program: self.associated_token_program.to_account_info(),
-            system_program: self.system_program.to_account_info(),
-            rent: self.rent.to_account_info(),
+            token_program: self.common.token_program.to_account_info(),
+            token_0_program,
+            token_1_program,
+            associated_token_program: self.common.associated_token_program.to_account_info(),
+            system_program: self.common.system_program.to_account_info(),
+            rent: self.common.rent.to_account_info(),
         };
-        let cpi_context = CpiContext::new(self.cp_swap_program.to_account_info(), cpi_accounts);
-        cpi::initialize(cpi_context, init_amount_0, init_amount_1, open_time)?;
-        self.token_state.migrated = true;
-        self.token_state.migration_timestamp = Clock::get()?.unix_timestamp;
-
-        Ok(())
-    }
-
-    pub fn prepare_liquidity(&mut self, init_amount_0: u64, init_amount_1: u64) -> Result<()> {
-        let token_state_seeds: &[&[&[u8]]] = &[&[
-            b"token_state",
-            self.token_state.mint.as_ref(),
-            &[self.token_state.bump],
-        ]];
-
-        // Determine which token is our custom token
-        let is_custom_token_first = self.token_0_mint.key() == self.token_state.mint;
-
-        if is_custom_token_first {
-            // Token 0 is custom token, Token 1 is WSOL
-
-            // Transfer custom tokens from token_vault to creator_token_0
-            token::transfer(
-                CpiContext::new_with_signer(
-                    self.token_program.to_account_info(),
-                    token::Transfer {
-                        from: self.token_vault.to_account_info(),
-                        to: self.creator_token_0.to_account_info(),
-                        authority: self.token_state.to_account_info(),
-                    },
-                    token_state_seeds,
-                ),
-                init_amount_0,
-            )?;
-
-            // Transfer WSOL from vault_wsol_account to creator_token_1
-            token::transfer(
-                CpiContext::new_with_signer(
-                    self.token_program.to_account_info(),
-                    token::Transfer {
-                        from: self.vault_wsol_account.to_account_info(),
-                        to: self.creator_token_1.to_account_info(),
-                        authority: self.token_state.to_account_info(),
-                    },
-                    token_state_seeds,
-                ),
-                init_amount_1,
-            )?;
-        } else {
-            // Token 0 is WSOL, Token 1 is custom token
-
-            // Transfer WSOL from vault_wsol_account to creator_token_0
-            token::transfer(
-                CpiContext::new_with_signer(
-                    self.token_program.to_account_info(),
-                    token::Transfer {
-                        from: self.vault_wsol_account.to_account_info(),
-                        to: self.creator_token_0.to_account_info(),
-                        authority: self.token_state.to_account_info(),
-                    },
-                    token_state_seeds,
-                ),
-                init_amount_0,
-            )?;
-
-            // Transfer custom tokens from token_vault to creator_token_1
-            token::transfer(
-                CpiContext::new_with_signer(
-                    self.token_program.to_account_info(),
-                    token::Transfer {
-                        from: self.token_vault.to_account_info(),
-                        to: self.creator_token_1.to_account_info(),
-                        authority: self.token_state.to_account_info(),
-                    },
-                    token_state_seeds,
-                ),
-                init_amount_1,
-            )?;
-        }
-
-        msg!(
-            "Liquidity prepared: {} token0, {} token1",
-            init_amount_0,
-            init_amount_1
-        );
-        Ok(())
-    }
-
-    pub fn wrap_sol(&mut self) -> Result<()> {
-        // transfer sol to token account
-
-        // let creator_mint = &self.token_state.mint.key();
-        // let signer_seeds: &[&[&[u8]]] = &[&[
-        //     b"token_state",
-        //     creator_mint.as_ref(),
-        //     &[self.token_state.bump],
-        // ]];
-
-        let token_vault = self.token_vault.key();
-
-        let sol_vault_seeds: &[&[&[u8]]] = &[&[
-            b"sol_vault",
-            token_vault.as_ref(), // Use token_vault.key() to match CreateToken!
-            &[self.token_state.sol_vault_bump],
-        ]];
-
+        let mint = self.common.token_mint.key();
+        let pool_creator_seeds: &[&[&[u8]]] =
+            &[&[b"pool_creator", mint.as_ref(), &[bumps.pool_creator]]];
         let cpi_context = CpiContext::new_with_signer(
-            self.system_program.to_account_info(),
-            Transfer {
-                from: self.sol_vault.to_account_info(),
-                to: self.vault_wsol_account.to_account_info(),
-            },
-            sol_vault_seeds,
+            self.cp_swap_program.to_account_info(),
+            cpi_accounts,
+            pool_creator_seeds,
         );
+        cpi::initialize(cpi_context, init_amount_0, init_amount_1, open_time)?;
 
-        let amount = self.sol_vault.to_account_info().lamports();
-        transfer(cpi_context, amount)?;
+        self.common.hand_over_position(
+            &self.lp_mint.to_account_info(),
+            &self.pool_creator_lp_token.to_account_info(),
+            &self.creator_lp_token.to_account_info(),
+            &self.common.token_program.to_account_info(),
+            bumps.pool_creator,
+        )?;
 
-        // Sync the native token to reflect the new SOL balance as wSOL
-        let cpi_accounts = token::SyncNative {
-            account: self.vault_wsol_account.to_account_info(),
-        };
-        let cpi_program = self.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::sync_native(cpi_ctx)?;
-        Ok(())
+        Ok(self.pool_state.key())
     }
 
-    pub fn calculate_current_market_cap(&self) -> Result<u64> {
-        const BASE_PRICE_PER_MILLION: u64 = 50;
-        const MAX_PRICE_PER_MILLION: u64 = 450;
-        const PRICE_RANGE: u64 = MAX_PRICE_PER_MILLION - BASE_PRICE_PER_MILLION;
-
-        let total_base_units = TOTAL_SUPPLY * 1_000_000_000;
-        let migration_base_units = (total_base_units / 100) * MIGRATION_THRESHOLD_PCT;
-
-        let progress = (self.token_state.tokens_sold * 1000) / migration_base_units;
-        let capped_progress = min(progress, 1000);
-        let sqrt_progress = integer_sqrt(capped_progress)?;
-
-        let price_per_million = BASE_PRICE_PER_MILLION + (PRICE_RANGE * sqrt_progress / 31);
-        let total_millions = total_base_units / 1_000_000;
-        let market_cap_lamports = price_per_million * total_millions;
-
-        Ok(market_cap_lamports)
+    fn pool_vaults(&self) -> (AccountInfo<'info>, AccountInfo<'info>) {
+        self.common.unsorted(
+            self.token_0_vault.to_account_info(),
+            self.token_1_vault.to_account_info(),
+        )
     }
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct LaunchParam {
-    pub token_mint: Pubkey,
     pub time: Option<i64>,
 }
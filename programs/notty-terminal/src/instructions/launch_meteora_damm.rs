@@ -0,0 +1,257 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+use anchor_spl::token_2022::Token2022;
+
+use super::migration_common::*;
+use crate::{error::NottyTerminalError, MigrationVenue};
+
+// DAMM v2 is deployed at the same address on devnet and mainnet
+pub const METEORA_DAMM_PROGRAM_ID: Pubkey = pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+
+/// Graduates into a Meteora DAMM v2 pool with the launch token as token a and WSOL as
+/// token b. The pool creator pays for the pool, the position NFT of the initial
+/// liquidity is minted straight to the creator.
+#[derive(Accounts)]
+pub struct LaunchMeteoraDamm<'info> {
+    pub common: MigrationCommon<'info>,
+
+    /// CHECK: Meteora DAMM v2 program
+    #[account(address = METEORA_DAMM_PROGRAM_ID)]
+    pub damm_program: UncheckedAccount<'info>,
+
+    /// CHECK: pool config, validated by the damm program
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: pool authority of the damm program
+    #[account(
+        seeds = [b"pool_authority"],
+        seeds::program = damm_program.key(),
+        bump,
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: pool state, init by the damm program. Checked against the address derived
+    /// from the config and the mints before the pool is created
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// position NFT mint, a fresh keypair init by the damm program
+    #[account(mut)]
+    pub position_nft_mint: Signer<'info>,
+
+    /// CHECK: creator account of the position NFT, init by the damm program
+    #[account(
+        mut,
+        seeds = [b"position_nft_account", position_nft_mint.key().as_ref()],
+        seeds::program = damm_program.key(),
+        bump,
+    )]
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    /// CHECK: position state, init by the damm program
+    #[account(
+        mut,
+        seeds = [b"position", position_nft_mint.key().as_ref()],
+        seeds::program = damm_program.key(),
+        bump,
+    )]
+    pub position: UncheckedAccount<'info>,
+
+    /// CHECK: token a (launch token) vault, init by the damm program. Checked against the
+    /// address derived from the pool with it
+    #[account(mut)]
+    pub token_a_vault: UncheckedAccount<'info>,
+
+    /// CHECK: token b (WSOL) vault, init by the damm program. Checked against the address
+    /// derived from the pool with it
+    #[account(mut)]
+    pub token_b_vault: UncheckedAccount<'info>,
+
+    /// CHECK: event authority of the damm program
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = damm_program.key(),
+        bump,
+    )]
+    pub event_authority: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+impl<'info> MigrationAdapter<'info> for LaunchMeteoraDamm<'info> {
+    const VENUE: MigrationVenue = MigrationVenue::MeteoraDamm;
+
+    // no create pool fee, ~0.03 SOL of pool and position account rent
+    const POOL_CREATION_BUDGET: u64 = 200_000_000;
+
+    type Params = LaunchMeteoraDammParam;
+
+    fn common(&mut self) -> &mut MigrationCommon<'info> {
+        &mut self.common
+    }
+
+    fn create_pool(
+        &mut self,
+        token_amount: u64,
+        sol_amount: u64,
+        params: LaunchMeteoraDammParam,
+        bumps: &MigrationCommonBumps,
+    ) -> Result<Pubkey> {
+        // damm keys its pools by the larger mint first
+        let (smaller_mint, larger_mint) = self.common.sorted_mint_keys();
+        let (pool, _) = Pubkey::find_program_address(
+            &[
+                b"pool",
+                self.config.key().as_ref(),
+                larger_mint.as_ref(),
+                smaller_mint.as_ref(),
+            ],
+            &self.damm_program.key(),
+        );
+        require_keys_eq!(
+            pool,
+            self.pool.key(),
+            NottyTerminalError::InvalidPoolAddress
+        );
+        for (mint, vault) in [
+            (self.common.token_mint.key(), self.token_a_vault.key()),
+            (self.common.wsol_mint.key(), self.token_b_vault.key()),
+        ] {
+            let (expected_vault, _) = Pubkey::find_program_address(
+                &[b"token_vault", mint.as_ref(), pool.as_ref()],
+                &self.damm_program.key(),
+            );
+            require_keys_eq!(
+                expected_vault,
+                vault,
+                NottyTerminalError::InvalidPoolAddress
+            );
+        }
+
+        let sqrt_price = sqrt_price_x64(token_amount, sol_amount)?;
+        let liquidity = full_range_liquidity(token_amount, sol_amount, sqrt_price)?;
+        let data = initialize_pool_data(liquidity, sqrt_price, params.activation_point)?;
+
+        let mint = self.common.token_mint.key();
+        invoke_signed(
+            &Instruction {
+                program_id: self.damm_program.key(),
+                accounts: vec![
+                    AccountMeta::new_readonly(self.common.creator.key(), false),
+                    AccountMeta::new(self.position_nft_mint.key(), true),
+                    AccountMeta::new(self.position_nft_account.key(), false),
+                    AccountMeta::new(self.common.pool_creator.key(), true),
+                    AccountMeta::new_readonly(self.config.key(), false),
+                    AccountMeta::new_readonly(self.pool_authority.key(), false),
+                    AccountMeta::new(self.pool.key(), false),
+                    AccountMeta::new(self.position.key(), false),
+                    AccountMeta::new_readonly(self.common.token_mint.key(), false),
+                    AccountMeta::new_readonly(self.common.wsol_mint.key(), false),
+                    AccountMeta::new(self.token_a_vault.key(), false),
+                    AccountMeta::new(self.token_b_vault.key(), false),
+                    AccountMeta::new(self.common.pool_creator_token_account.key(), false),
+                    AccountMeta::new(self.common.pool_creator_wsol_account.key(), false),
+                    AccountMeta::new_readonly(self.common.launch_token_program.key(), false),
+                    AccountMeta::new_readonly(self.common.token_program.key(), false),
+                    AccountMeta::new_readonly(self.token_2022_program.key(), false),
+                    AccountMeta::new_readonly(self.common.system_program.key(), false),
+                    AccountMeta::new_readonly(self.event_authority.key(), false),
+                    AccountMeta::new_readonly(self.damm_program.key(), false),
+                ],
+                data,
+            },
+            &[
+                self.common.creator.to_account_info(),
+                self.common.pool_creator.to_account_info(),
+                self.position_nft_mint.to_account_info(),
+                self.position_nft_account.to_account_info(),
+                self.config.to_account_info(),
+                self.pool_authority.to_account_info(),
+                self.pool.to_account_info(),
+                self.position.to_account_info(),
+                self.common.token_mint.to_account_info(),
+                self.common.wsol_mint.to_account_info(),
+                self.token_a_vault.to_account_info(),
+                self.token_b_vault.to_account_info(),
+                self.common.pool_creator_token_account.to_account_info(),
+                self.common.pool_creator_wsol_account.to_account_info(),
+                self.common.launch_token_program.to_account_info(),
+                self.common.token_program.to_account_info(),
+                self.token_2022_program.to_account_info(),
+                self.common.system_program.to_account_info(),
+                self.event_authority.to_account_info(),
+                self.damm_program.to_account_info(),
+            ],
+            &[&[b"pool_creator", mint.as_ref(), &[bumps.pool_creator]]],
+        )?;
+
+        Ok(self.pool.key())
+    }
+
+    fn pool_vaults(&self) -> (AccountInfo<'info>, AccountInfo<'info>) {
+        (
+            self.token_a_vault.to_account_info(),
+            self.token_b_vault.to_account_info(),
+        )
+    }
+}
+
+/// `initialize_pool` instruction data
+fn initialize_pool_data(
+    liquidity: u128,
+    sqrt_price: u128,
+    activation_point: Option<u64>,
+) -> Result<Vec<u8>> {
+    // damm keeps liquidity with 64 extra fractional bits
+    require!(
+        liquidity.leading_zeros() >= 64,
+        NottyTerminalError::NumericalOverflow
+    );
+
+    let mut data = anchor_discriminator("initialize_pool").to_vec();
+    data.extend_from_slice(&(liquidity << 64).to_le_bytes());
+    data.extend_from_slice(&sqrt_price.to_le_bytes());
+    activation_point.serialize(&mut data)?;
+    Ok(data)
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct LaunchMeteoraDammParam {
+    pub activation_point: Option<u64>, // None activates the pool right away
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initialize_pool_data_layout() {
+        let data = initialize_pool_data(2_000_000_000, 1 << 64, None).unwrap();
+
+        assert_eq!(data.len(), 8 + 16 + 16 + 1);
+        assert_eq!(data[..8], [95, 180, 10, 172, 84, 174, 232, 40]);
+        assert_eq!(data[8..24], (2_000_000_000u128 << 64).to_le_bytes());
+        assert_eq!(data[24..40], (1u128 << 64).to_le_bytes());
+        assert_eq!(data[40], 0);
+    }
+
+    #[test]
+    fn initialize_pool_data_with_activation_point() {
+        let data = initialize_pool_data(1, 1 << 64, Some(1_700_000_000)).unwrap();
+
+        assert_eq!(data.len(), 8 + 16 + 16 + 1 + 8);
+        assert_eq!(data[40], 1);
+        assert_eq!(data[41..49], 1_700_000_000u64.to_le_bytes());
+    }
+
+    #[test]
+    fn initialize_pool_data_rejects_liquidity_past_64_bits() {
+        let err = initialize_pool_data(1 << 64, 1 << 64, None).unwrap_err();
+        assert_eq!(err, NottyTerminalError::NumericalOverflow.into());
+    }
+}
@@ -0,0 +1,455 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::{invoke, invoke_signed},
+    },
+};
+use anchor_spl::associated_token::get_associated_token_address;
+
+use super::migration_common::*;
+use crate::{error::NottyTerminalError, MigrationVenue};
+
+// whirlpools is deployed at the same address on devnet and mainnet
+pub const ORCA_WHIRLPOOL_PROGRAM_ID: Pubkey =
+    pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+
+/// Largest tick index supported by whirlpools
+const WHIRLPOOL_MAX_TICK_INDEX: i32 = 443_636;
+
+/// Ticks held by one whirlpool tick array
+const TICK_ARRAY_SIZE: i32 = 88;
+
+/// Graduates into an Orca whirlpool with a single full range position, opened by the
+/// pool creator and handed to the creator. Token a/b follow the whirlpool ordering
+/// (mint_a < mint_b). The pool is new, so the tick arrays holding both full range ticks
+/// are initialized here as well.
+#[derive(Accounts)]
+pub struct LaunchOrcaWhirlpool<'info> {
+    pub common: MigrationCommon<'info>,
+
+    /// CHECK: Orca whirlpool program
+    #[account(address = ORCA_WHIRLPOOL_PROGRAM_ID)]
+    pub whirlpool_program: UncheckedAccount<'info>,
+
+    /// CHECK: whirlpools config, validated by the whirlpool program
+    pub whirlpools_config: UncheckedAccount<'info>,
+
+    /// CHECK: fee tier matching the tick spacing, validated by the whirlpool program
+    pub fee_tier: UncheckedAccount<'info>,
+
+    /// CHECK: pool state, init by the whirlpool program
+    #[account(mut)]
+    pub whirlpool: UncheckedAccount<'info>,
+
+    /// token a vault of the pool, a fresh keypair init by the whirlpool program
+    #[account(mut)]
+    pub token_vault_a: Signer<'info>,
+
+    /// token b vault of the pool, a fresh keypair init by the whirlpool program
+    #[account(mut)]
+    pub token_vault_b: Signer<'info>,
+
+    /// CHECK: position state, init by the whirlpool program
+    #[account(
+        mut,
+        seeds = [b"position", position_mint.key().as_ref()],
+        seeds::program = whirlpool_program.key(),
+        bump,
+    )]
+    pub position: UncheckedAccount<'info>,
+
+    /// position NFT mint, a fresh keypair init by the whirlpool program
+    #[account(mut)]
+    pub position_mint: Signer<'info>,
+
+    /// CHECK: pool creator ATA of the position NFT, init by the whirlpool program and
+    /// closed once the NFT is handed to the creator
+    #[account(
+        mut,
+        address = get_associated_token_address(&common.pool_creator.key(), &position_mint.key()),
+    )]
+    pub position_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: creator ATA of the position NFT, created once the NFT is minted
+    #[account(
+        mut,
+        address = get_associated_token_address(&common.creator.key(), &position_mint.key()),
+    )]
+    pub creator_position_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: tick array containing the lower full range tick, init by the whirlpool program.
+    /// Checked against the address derived from its start tick before it is initialized
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: tick array containing the upper full range tick, init by the whirlpool program.
+    /// Checked against the address derived from its start tick before it is initialized
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+}
+
+impl<'info> LaunchOrcaWhirlpool<'info> {
+    fn initialize_pool(&self, tick_spacing: u16, initial_sqrt_price: u128) -> Result<()> {
+        let (token_mint_a, token_mint_b) = self.common.sorted_mints();
+
+        let (whirlpool, whirlpool_bump) = Pubkey::find_program_address(
+            &[
+                b"whirlpool",
+                self.whirlpools_config.key().as_ref(),
+                token_mint_a.key().as_ref(),
+                token_mint_b.key().as_ref(),
+                &tick_spacing.to_le_bytes(),
+            ],
+            &self.whirlpool_program.key(),
+        );
+        require_keys_eq!(
+            whirlpool,
+            self.whirlpool.key(),
+            NottyTerminalError::InvalidPoolAddress
+        );
+
+        let data = initialize_pool_data(whirlpool_bump, tick_spacing, initial_sqrt_price);
+
+        invoke(
+            &Instruction {
+                program_id: self.whirlpool_program.key(),
+                accounts: vec![
+                    AccountMeta::new_readonly(self.whirlpools_config.key(), false),
+                    AccountMeta::new_readonly(token_mint_a.key(), false),
+                    AccountMeta::new_readonly(token_mint_b.key(), false),
+                    AccountMeta::new(self.common.signer.key(), true),
+                    AccountMeta::new(self.whirlpool.key(), false),
+                    AccountMeta::new(self.token_vault_a.key(), true),
+                    AccountMeta::new(self.token_vault_b.key(), true),
+                    AccountMeta::new_readonly(self.fee_tier.key(), false),
+                    AccountMeta::new_readonly(self.common.token_program.key(), false),
+                    AccountMeta::new_readonly(self.common.system_program.key(), false),
+                    AccountMeta::new_readonly(self.common.rent.key(), false),
+                ],
+                data,
+            },
+            &[
+                self.whirlpools_config.to_account_info(),
+                token_mint_a,
+                token_mint_b,
+                self.common.signer.to_account_info(),
+                self.whirlpool.to_account_info(),
+                self.token_vault_a.to_account_info(),
+                self.token_vault_b.to_account_info(),
+                self.fee_tier.to_account_info(),
+                self.common.token_program.to_account_info(),
+                self.common.system_program.to_account_info(),
+                self.common.rent.to_account_info(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn initialize_tick_array(
+        &self,
+        tick_array: AccountInfo<'info>,
+        start_tick_index: i32,
+    ) -> Result<()> {
+        let (expected_tick_array, _) = Pubkey::find_program_address(
+            &[
+                b"tick_array",
+                self.whirlpool.key().as_ref(),
+                start_tick_index.to_string().as_bytes(),
+            ],
+            &self.whirlpool_program.key(),
+        );
+        require_keys_eq!(
+            expected_tick_array,
+            tick_array.key(),
+            NottyTerminalError::InvalidPoolAddress
+        );
+
+        invoke(
+            &Instruction {
+                program_id: self.whirlpool_program.key(),
+                accounts: vec![
+                    AccountMeta::new_readonly(self.whirlpool.key(), false),
+                    AccountMeta::new(self.common.signer.key(), true),
+                    AccountMeta::new(tick_array.key(), false),
+                    AccountMeta::new_readonly(self.common.system_program.key(), false),
+                ],
+                data: initialize_tick_array_data(start_tick_index),
+            },
+            &[
+                self.whirlpool.to_account_info(),
+                self.common.signer.to_account_info(),
+                tick_array,
+                self.common.system_program.to_account_info(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn open_full_range_position(&self, tick_lower_index: i32, tick_upper_index: i32) -> Result<()> {
+        let (_, position_bump) = Pubkey::find_program_address(
+            &[b"position", self.position_mint.key().as_ref()],
+            &self.whirlpool_program.key(),
+        );
+
+        let data = open_position_data(position_bump, tick_lower_index, tick_upper_index);
+
+        invoke(
+            &Instruction {
+                program_id: self.whirlpool_program.key(),
+                accounts: vec![
+                    AccountMeta::new(self.common.signer.key(), true),
+                    AccountMeta::new_readonly(self.common.pool_creator.key(), false),
+                    AccountMeta::new(self.position.key(), false),
+                    AccountMeta::new(self.position_mint.key(), true),
+                    AccountMeta::new(self.position_token_account.key(), false),
+                    AccountMeta::new_readonly(self.whirlpool.key(), false),
+                    AccountMeta::new_readonly(self.common.token_program.key(), false),
+                    AccountMeta::new_readonly(self.common.system_program.key(), false),
+                    AccountMeta::new_readonly(self.common.rent.key(), false),
+                    AccountMeta::new_readonly(self.common.associated_token_program.key(), false),
+                ],
+                data,
+            },
+            &[
+                self.common.signer.to_account_info(),
+                self.common.pool_creator.to_account_info(),
+                self.position.to_account_info(),
+                self.position_mint.to_account_info(),
+                self.position_token_account.to_account_info(),
+                self.whirlpool.to_account_info(),
+                self.common.token_program.to_account_info(),
+                self.common.system_program.to_account_info(),
+                self.common.rent.to_account_info(),
+                self.common.associated_token_program.to_account_info(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn increase_liquidity(
+        &self,
+        liquidity: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+        pool_creator_bump: u8,
+    ) -> Result<()> {
+        let (token_owner_account_a, token_owner_account_b) =
+            self.common.sorted_pool_creator_accounts();
+
+        let data = increase_liquidity_data(liquidity, token_max_a, token_max_b);
+
+        let mint = self.common.token_mint.key();
+        invoke_signed(
+            &Instruction {
+                program_id: self.whirlpool_program.key(),
+                accounts: vec![
+                    AccountMeta::new(self.whirlpool.key(), false),
+                    AccountMeta::new_readonly(self.common.token_program.key(), false),
+                    AccountMeta::new_readonly(self.common.pool_creator.key(), true),
+                    AccountMeta::new(self.position.key(), false),
+                    AccountMeta::new_readonly(self.position_token_account.key(), false),
+                    AccountMeta::new(token_owner_account_a.key(), false),
+                    AccountMeta::new(token_owner_account_b.key(), false),
+                    AccountMeta::new(self.token_vault_a.key(), false),
+                    AccountMeta::new(self.token_vault_b.key(), false),
+                    AccountMeta::new(self.tick_array_lower.key(), false),
+                    AccountMeta::new(self.tick_array_upper.key(), false),
+                ],
+                data,
+            },
+            &[
+                self.whirlpool.to_account_info(),
+                self.common.token_program.to_account_info(),
+                self.common.pool_creator.to_account_info(),
+                self.position.to_account_info(),
+                self.position_token_account.to_account_info(),
+                token_owner_account_a,
+                token_owner_account_b,
+                self.token_vault_a.to_account_info(),
+                self.token_vault_b.to_account_info(),
+                self.tick_array_lower.to_account_info(),
+                self.tick_array_upper.to_account_info(),
+            ],
+            &[&[b"pool_creator", mint.as_ref(), &[pool_creator_bump]]],
+        )?;
+
+        Ok(())
+    }
+}
+
+impl<'info> MigrationAdapter<'info> for LaunchOrcaWhirlpool<'info> {
+    const VENUE: MigrationVenue = MigrationVenue::OrcaWhirlpool;
+
+    // the signer funds the pool accounts directly, the pool creator only signs
+    const POOL_CREATION_BUDGET: u64 = 0;
+
+    type Params = LaunchOrcaWhirlpoolParam;
+
+    fn common(&mut self) -> &mut MigrationCommon<'info> {
+        &mut self.common
+    }
+
+    fn create_pool(
+        &mut self,
+        token_amount: u64,
+        sol_amount: u64,
+        params: LaunchOrcaWhirlpoolParam,
+        bumps: &MigrationCommonBumps,
+    ) -> Result<Pubkey> {
+        self.common.require_spl_token_launch()?;
+
+        let (amount_a, amount_b) = self.common.sorted_amounts(token_amount, sol_amount);
+
+        let initial_sqrt_price = sqrt_price_x64(amount_a, amount_b)?;
+        let liquidity = full_range_liquidity(amount_a, amount_b, initial_sqrt_price)?;
+
+        let (tick_lower_index, tick_upper_index) = full_range_ticks(params.tick_spacing);
+
+        self.initialize_pool(params.tick_spacing, initial_sqrt_price)?;
+        self.initialize_tick_array(
+            self.tick_array_lower.to_account_info(),
+            tick_array_start_index(tick_lower_index, params.tick_spacing),
+        )?;
+        self.initialize_tick_array(
+            self.tick_array_upper.to_account_info(),
+            tick_array_start_index(tick_upper_index, params.tick_spacing),
+        )?;
+        self.open_full_range_position(tick_lower_index, tick_upper_index)?;
+        self.increase_liquidity(liquidity, amount_a, amount_b, bumps.pool_creator)?;
+
+        self.common.hand_over_position(
+            &self.position_mint.to_account_info(),
+            &self.position_token_account.to_account_info(),
+            &self.creator_position_token_account.to_account_info(),
+            &self.common.token_program.to_account_info(),
+            bumps.pool_creator,
+        )?;
+
+        Ok(self.whirlpool.key())
+    }
+
+    fn pool_vaults(&self) -> (AccountInfo<'info>, AccountInfo<'info>) {
+        self.common.unsorted(
+            self.token_vault_a.to_account_info(),
+            self.token_vault_b.to_account_info(),
+        )
+    }
+}
+
+/// Widest (lower, upper) tick range usable with the tick spacing
+fn full_range_ticks(tick_spacing: u16) -> (i32, i32) {
+    let tick_upper_index = WHIRLPOOL_MAX_TICK_INDEX / tick_spacing as i32 * tick_spacing as i32;
+    (-tick_upper_index, tick_upper_index)
+}
+
+/// First tick of the array holding `tick_index`
+fn tick_array_start_index(tick_index: i32, tick_spacing: u16) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    tick_index.div_euclid(ticks_in_array) * ticks_in_array
+}
+
+fn initialize_pool_data(
+    whirlpool_bump: u8,
+    tick_spacing: u16,
+    initial_sqrt_price: u128,
+) -> Vec<u8> {
+    let mut data = anchor_discriminator("initialize_pool").to_vec();
+    data.push(whirlpool_bump);
+    data.extend_from_slice(&tick_spacing.to_le_bytes());
+    data.extend_from_slice(&initial_sqrt_price.to_le_bytes());
+    data
+}
+
+fn initialize_tick_array_data(start_tick_index: i32) -> Vec<u8> {
+    let mut data = anchor_discriminator("initialize_tick_array").to_vec();
+    data.extend_from_slice(&start_tick_index.to_le_bytes());
+    data
+}
+
+fn open_position_data(position_bump: u8, tick_lower_index: i32, tick_upper_index: i32) -> Vec<u8> {
+    let mut data = anchor_discriminator("open_position").to_vec();
+    data.push(position_bump);
+    data.extend_from_slice(&tick_lower_index.to_le_bytes());
+    data.extend_from_slice(&tick_upper_index.to_le_bytes());
+    data
+}
+
+fn increase_liquidity_data(liquidity: u128, token_max_a: u64, token_max_b: u64) -> Vec<u8> {
+    let mut data = anchor_discriminator("increase_liquidity").to_vec();
+    data.extend_from_slice(&liquidity.to_le_bytes());
+    data.extend_from_slice(&token_max_a.to_le_bytes());
+    data.extend_from_slice(&token_max_b.to_le_bytes());
+    data
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct LaunchOrcaWhirlpoolParam {
+    pub tick_spacing: u16, // must match the fee tier
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_range_ticks_are_multiples_of_the_spacing() {
+        assert_eq!(full_range_ticks(1), (-443_636, 443_636));
+        assert_eq!(full_range_ticks(64), (-443_584, 443_584));
+        assert_eq!(full_range_ticks(128), (-443_520, 443_520));
+    }
+
+    #[test]
+    fn tick_arrays_hold_the_full_range_ticks() {
+        // 88 ticks of 64 per array, the lower array starts below the lowest tick
+        assert_eq!(tick_array_start_index(-443_584, 64), -444_928);
+        assert_eq!(tick_array_start_index(443_584, 64), 439_296);
+        assert_eq!(tick_array_start_index(0, 64), 0);
+        assert_eq!(tick_array_start_index(-1, 64), -5_632);
+    }
+
+    #[test]
+    fn initialize_tick_array_data_layout() {
+        let data = initialize_tick_array_data(-444_928);
+
+        assert_eq!(data.len(), 8 + 4);
+        assert_eq!(data[..8], [11, 188, 193, 214, 141, 91, 149, 184]);
+        assert_eq!(data[8..12], (-444_928i32).to_le_bytes());
+    }
+
+    #[test]
+    fn initialize_pool_data_layout() {
+        let data = initialize_pool_data(253, 64, 1 << 64);
+
+        assert_eq!(data.len(), 8 + 1 + 2 + 16);
+        assert_eq!(data[..8], [95, 180, 10, 172, 84, 174, 232, 40]);
+        assert_eq!(data[8], 253);
+        assert_eq!(data[9..11], [64, 0]);
+        assert_eq!(data[11..27], (1u128 << 64).to_le_bytes());
+    }
+
+    #[test]
+    fn open_position_data_layout() {
+        let data = open_position_data(252, -443_584, 443_584);
+
+        assert_eq!(data.len(), 8 + 1 + 4 + 4);
+        assert_eq!(data[..8], [135, 128, 47, 77, 15, 152, 240, 49]);
+        assert_eq!(data[8], 252);
+        assert_eq!(data[9..13], (-443_584i32).to_le_bytes());
+        assert_eq!(data[13..17], 443_584i32.to_le_bytes());
+    }
+
+    #[test]
+    fn increase_liquidity_data_layout() {
+        let data = increase_liquidity_data(2_000_000_000, 1_000_000_000, 4_000_000_000);
+
+        assert_eq!(data.len(), 8 + 16 + 8 + 8);
+        assert_eq!(data[..8], [46, 156, 243, 118, 13, 205, 251, 178]);
+        assert_eq!(data[8..24], 2_000_000_000u128.to_le_bytes());
+        assert_eq!(data[24..32], 1_000_000_000u64.to_le_bytes());
+        assert_eq!(data[32..40], 4_000_000_000u64.to_le_bytes());
+    }
+}
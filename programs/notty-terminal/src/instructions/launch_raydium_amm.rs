@@ -0,0 +1,290 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+use anchor_spl::associated_token::get_associated_token_address;
+
+use super::migration_common::*;
+use crate::{error::NottyTerminalError, MigrationVenue};
+
+#[cfg(feature = "devnet")]
+pub const RAYDIUM_AMM_V4_PROGRAM_ID: Pubkey =
+    pubkey!("HWy1jotHpo6UqeQxx49dpYYdQB8wj9Qk9MdxwjLvDHB8");
+#[cfg(not(feature = "devnet"))]
+pub const RAYDIUM_AMM_V4_PROGRAM_ID: Pubkey =
+    pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+
+/// `AmmInstruction::Initialize2` tag in the Raydium AMM v4 program
+const INITIALIZE2_TAG: u8 = 1;
+
+/// Graduates into a Raydium AMM v4 pool. The launch token is the coin side and WSOL the
+/// pc side, the OpenBook market must already exist for the pair.
+#[derive(Accounts)]
+pub struct LaunchRaydiumAmm<'info> {
+    pub common: MigrationCommon<'info>,
+
+    /// CHECK: Raydium AMM v4 program
+    #[account(address = RAYDIUM_AMM_V4_PROGRAM_ID)]
+    pub amm_program: UncheckedAccount<'info>,
+
+    /// CHECK: pool state, init by the amm program. Checked against the address derived
+    /// from the market before the pool is created
+    #[account(mut)]
+    pub amm_pool: UncheckedAccount<'info>,
+
+    /// CHECK: pool vault and lp mint authority. Checked against the address derived from
+    /// the nonce before the pool is created
+    pub amm_authority: UncheckedAccount<'info>,
+
+    /// CHECK: open orders of the pool, init by the amm program
+    #[account(
+        mut,
+        seeds = [amm_program.key().as_ref(), market.key().as_ref(), b"open_order_associated_seed"],
+        seeds::program = amm_program.key(),
+        bump,
+    )]
+    pub amm_open_orders: UncheckedAccount<'info>,
+
+    /// CHECK: pool lp mint, init by the amm program
+    #[account(
+        mut,
+        seeds = [amm_program.key().as_ref(), market.key().as_ref(), b"lp_mint_associated_seed"],
+        seeds::program = amm_program.key(),
+        bump,
+    )]
+    pub amm_lp_mint: UncheckedAccount<'info>,
+
+    /// CHECK: coin (launch token) vault, init by the amm program
+    #[account(
+        mut,
+        seeds = [amm_program.key().as_ref(), market.key().as_ref(), b"coin_vault_associated_seed"],
+        seeds::program = amm_program.key(),
+        bump,
+    )]
+    pub amm_coin_vault: UncheckedAccount<'info>,
+
+    /// CHECK: pc (WSOL) vault, init by the amm program
+    #[account(
+        mut,
+        seeds = [amm_program.key().as_ref(), market.key().as_ref(), b"pc_vault_associated_seed"],
+        seeds::program = amm_program.key(),
+        bump,
+    )]
+    pub amm_pc_vault: UncheckedAccount<'info>,
+
+    /// CHECK: target orders, init by the amm program
+    #[account(
+        mut,
+        seeds = [amm_program.key().as_ref(), market.key().as_ref(), b"target_associated_seed"],
+        seeds::program = amm_program.key(),
+        bump,
+    )]
+    pub amm_target_orders: UncheckedAccount<'info>,
+
+    /// CHECK: amm config of the amm program
+    #[account(
+        seeds = [b"amm_config_account_seed"],
+        seeds::program = amm_program.key(),
+        bump,
+    )]
+    pub amm_config: UncheckedAccount<'info>,
+
+    /// CHECK: create pool fee destination, validated by the amm program
+    #[account(mut)]
+    pub create_fee_destination: UncheckedAccount<'info>,
+
+    /// CHECK: OpenBook program, validated by the amm program
+    pub market_program: UncheckedAccount<'info>,
+
+    /// CHECK: OpenBook market of the pair, validated by the amm program
+    pub market: UncheckedAccount<'info>,
+
+    /// CHECK: pool creator lp ATA, init by the amm program and closed once the LP
+    /// tokens are handed to the creator
+    #[account(
+        mut,
+        address = get_associated_token_address(&common.pool_creator.key(), &amm_lp_mint.key()),
+    )]
+    pub pool_creator_lp_token: UncheckedAccount<'info>,
+
+    /// CHECK: creator lp ATA, receives the LP tokens. Created once the lp mint exists
+    #[account(
+        mut,
+        address = get_associated_token_address(&common.creator.key(), &amm_lp_mint.key()),
+    )]
+    pub creator_lp_token: UncheckedAccount<'info>,
+}
+
+impl<'info> MigrationAdapter<'info> for LaunchRaydiumAmm<'info> {
+    const VENUE: MigrationVenue = MigrationVenue::RaydiumAmmV4;
+
+    // 0.4 SOL create pool fee and ~0.05 SOL of pool account rent
+    const POOL_CREATION_BUDGET: u64 = 1_000_000_000;
+
+    type Params = LaunchRaydiumAmmParam;
+
+    fn common(&mut self) -> &mut MigrationCommon<'info> {
+        &mut self.common
+    }
+
+    fn create_pool(
+        &mut self,
+        token_amount: u64,
+        sol_amount: u64,
+        params: LaunchRaydiumAmmParam,
+        bumps: &MigrationCommonBumps,
+    ) -> Result<Pubkey> {
+        self.common.require_spl_token_launch()?;
+
+        let (amm_pool, _) = Pubkey::find_program_address(
+            &[
+                self.amm_program.key().as_ref(),
+                self.market.key().as_ref(),
+                b"amm_associated_seed",
+            ],
+            &self.amm_program.key(),
+        );
+        require_keys_eq!(
+            amm_pool,
+            self.amm_pool.key(),
+            NottyTerminalError::InvalidPoolAddress
+        );
+
+        // the amm signs with the nonce it is given, not the canonical bump
+        let amm_authority = Pubkey::create_program_address(
+            &[b"amm authority", &[params.nonce]],
+            &self.amm_program.key(),
+        )
+        .map_err(|_| NottyTerminalError::InvalidPoolAddress)?;
+        require_keys_eq!(
+            amm_authority,
+            self.amm_authority.key(),
+            NottyTerminalError::InvalidPoolAddress
+        );
+
+        let open_time = match params.time {
+            Some(value) => value as u64,
+            None => Clock::get()?.unix_timestamp as u64,
+        };
+
+        let data = initialize2_data(params.nonce, open_time, sol_amount, token_amount);
+
+        let account_infos = [
+            self.common.token_program.to_account_info(),
+            self.common.associated_token_program.to_account_info(),
+            self.common.system_program.to_account_info(),
+            self.common.rent.to_account_info(),
+            self.amm_pool.to_account_info(),
+            self.amm_authority.to_account_info(),
+            self.amm_open_orders.to_account_info(),
+            self.amm_lp_mint.to_account_info(),
+            self.common.token_mint.to_account_info(),
+            self.common.wsol_mint.to_account_info(),
+            self.amm_coin_vault.to_account_info(),
+            self.amm_pc_vault.to_account_info(),
+            self.amm_target_orders.to_account_info(),
+            self.amm_config.to_account_info(),
+            self.create_fee_destination.to_account_info(),
+            self.market_program.to_account_info(),
+            self.market.to_account_info(),
+            self.common.pool_creator.to_account_info(),
+            self.common.pool_creator_token_account.to_account_info(),
+            self.common.pool_creator_wsol_account.to_account_info(),
+            self.pool_creator_lp_token.to_account_info(),
+        ];
+
+        let accounts = vec![
+            AccountMeta::new_readonly(self.common.token_program.key(), false),
+            AccountMeta::new_readonly(self.common.associated_token_program.key(), false),
+            AccountMeta::new_readonly(self.common.system_program.key(), false),
+            AccountMeta::new_readonly(self.common.rent.key(), false),
+            AccountMeta::new(self.amm_pool.key(), false),
+            AccountMeta::new_readonly(self.amm_authority.key(), false),
+            AccountMeta::new(self.amm_open_orders.key(), false),
+            AccountMeta::new(self.amm_lp_mint.key(), false),
+            AccountMeta::new_readonly(self.common.token_mint.key(), false),
+            AccountMeta::new_readonly(self.common.wsol_mint.key(), false),
+            AccountMeta::new(self.amm_coin_vault.key(), false),
+            AccountMeta::new(self.amm_pc_vault.key(), false),
+            AccountMeta::new(self.amm_target_orders.key(), false),
+            AccountMeta::new_readonly(self.amm_config.key(), false),
+            AccountMeta::new(self.create_fee_destination.key(), false),
+            AccountMeta::new_readonly(self.market_program.key(), false),
+            AccountMeta::new_readonly(self.market.key(), false),
+            AccountMeta::new(self.common.pool_creator.key(), true),
+            AccountMeta::new(self.common.pool_creator_token_account.key(), false),
+            AccountMeta::new(self.common.pool_creator_wsol_account.key(), false),
+            AccountMeta::new(self.pool_creator_lp_token.key(), false),
+        ];
+
+        let mint = self.common.token_mint.key();
+        invoke_signed(
+            &Instruction {
+                program_id: self.amm_program.key(),
+                accounts,
+                data,
+            },
+            &account_infos,
+            &[&[b"pool_creator", mint.as_ref(), &[bumps.pool_creator]]],
+        )?;
+
+        self.common.hand_over_position(
+            &self.amm_lp_mint.to_account_info(),
+            &self.pool_creator_lp_token.to_account_info(),
+            &self.creator_lp_token.to_account_info(),
+            &self.common.token_program.to_account_info(),
+            bumps.pool_creator,
+        )?;
+
+        Ok(self.amm_pool.key())
+    }
+
+    fn pool_vaults(&self) -> (AccountInfo<'info>, AccountInfo<'info>) {
+        (
+            self.amm_coin_vault.to_account_info(),
+            self.amm_pc_vault.to_account_info(),
+        )
+    }
+}
+
+/// `Initialize2` instruction data, the launch token is the coin side and WSOL the pc side
+fn initialize2_data(
+    nonce: u8,
+    open_time: u64,
+    init_pc_amount: u64,
+    init_coin_amount: u64,
+) -> Vec<u8> {
+    let mut data = Vec::with_capacity(26);
+    data.push(INITIALIZE2_TAG);
+    data.push(nonce);
+    data.extend_from_slice(&open_time.to_le_bytes());
+    data.extend_from_slice(&init_pc_amount.to_le_bytes());
+    data.extend_from_slice(&init_coin_amount.to_le_bytes());
+    data
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct LaunchRaydiumAmmParam {
+    pub nonce: u8, // bump of the amm authority
+    pub time: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initialize2_data_layout() {
+        let data = initialize2_data(254, 1_700_000_000, 450_000_000_000, 200_000_000_000_000_000);
+
+        assert_eq!(data.len(), 26);
+        assert_eq!(data[0], 1);
+        assert_eq!(data[1], 254);
+        assert_eq!(data[2..10], 1_700_000_000u64.to_le_bytes());
+        assert_eq!(data[10..18], 450_000_000_000u64.to_le_bytes());
+        assert_eq!(data[18..26], 200_000_000_000_000_000u64.to_le_bytes());
+    }
+}
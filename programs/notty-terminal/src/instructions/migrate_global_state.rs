@@ -0,0 +1,85 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{error::NottyTerminalError, GlobalState, LegacyGlobalState};
+
+/// One-off upgrade of a `GlobalState` created by 0.1.x. The account grows to the current
+/// layout, the admin tops up its rent and the new fields get the `initialize` defaults
+#[derive(Accounts)]
+pub struct MigrateGlobalState<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: legacy layout, decoded and checked by `LegacyGlobalState::try_from_account`
+    #[account(mut)]
+    pub global_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateGlobalState<'info> {
+    pub fn handle_migrate_global_state(&mut self, args: MigrateGlobalStateArgs) -> Result<()> {
+        let legacy = LegacyGlobalState::try_from_account(&self.global_state)?;
+
+        require_keys_eq!(
+            self.admin.key(),
+            legacy.admin,
+            NottyTerminalError::UnauthorizedAdmin
+        );
+        let global_state_key =
+            Pubkey::create_program_address(&[b"global_state", &[legacy.bump]], &crate::ID)
+                .map_err(|_| ErrorCode::ConstraintSeeds)?;
+        require_keys_eq!(
+            self.global_state.key(),
+            global_state_key,
+            ErrorCode::ConstraintSeeds
+        );
+
+        let global_state = legacy.into_current(args.amm_config);
+
+        grow_account(
+            self.global_state.to_account_info(),
+            self.admin.to_account_info(),
+            self.system_program.to_account_info(),
+            8 + GlobalState::INIT_SPACE,
+        )?;
+        global_state.try_serialize(&mut &mut self.global_state.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+}
+
+/// Reallocates a program account to `len` bytes, `payer` covers the extra rent
+pub fn grow_account<'info>(
+    account: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    len: usize,
+) -> Result<()> {
+    let rent_top_up = Rent::get()?
+        .minimum_balance(len)
+        .saturating_sub(account.lamports());
+
+    if rent_top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program,
+                Transfer {
+                    from: payer,
+                    to: account.clone(),
+                },
+            ),
+            rent_top_up,
+        )?;
+    }
+
+    account.resize(len)?;
+    Ok(())
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct MigrateGlobalStateArgs {
+    pub amm_config: Pubkey, // default Raydium CPMM config, 0.1.x hardcoded it
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use super::migrate_global_state::grow_account;
+use crate::{error::NottyTerminalError, GlobalState, LegacyTokenState, TokenState};
+
+/// One-off upgrade of a `TokenState` created by 0.1.x to the current versioned layout.
+/// The admin tops up the rent, curves keep trading (or stay migrated) as before
+#[derive(Accounts)]
+pub struct MigrateTokenState<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = admin @NottyTerminalError::UnauthorizedAdmin
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// CHECK: legacy layout, decoded and checked by `LegacyTokenState::try_from_account`
+    #[account(mut)]
+    pub token_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateTokenState<'info> {
+    pub fn handle_migrate_token_state(&mut self) -> Result<()> {
+        let legacy = LegacyTokenState::try_from_account(&self.token_state)?;
+
+        let token_state_key = Pubkey::create_program_address(
+            &[b"token_state", legacy.mint.as_ref(), &[legacy.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::ConstraintSeeds)?;
+        require_keys_eq!(
+            self.token_state.key(),
+            token_state_key,
+            ErrorCode::ConstraintSeeds
+        );
+
+        let token_state = legacy.into_current();
+
+        grow_account(
+            self.token_state.to_account_info(),
+            self.admin.to_account_info(),
+            self.system_program.to_account_info(),
+            8 + TokenState::INIT_SPACE,
+        )?;
+        token_state.try_serialize(&mut &mut self.token_state.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+}
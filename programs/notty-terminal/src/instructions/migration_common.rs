@@ -0,0 +1,720 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::hash::hash,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token::{spl_token, Token},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::NottyTerminalError, CreatorProfile, GlobalState, MigrationAmounts, MigrationStage,
+    MigrationVenue, TokenState,
+};
+
+use std::cmp::min;
+
+/// Accounts every venue needs to execute a prepared migration, regardless of the pool it creates
+#[derive(Accounts)]
+pub struct MigrationCommon<'info> {
+    /// Address paying the pool rent. Can be anyone
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: creator of the curve, receives the pool position (LP tokens or position NFT)
+    #[account(
+        constraint = token_state.creator.key() == creator.key() @NottyTerminalError::WrongCreator
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Program owned signer creating the pool in place of the creator. Holds no data,
+    /// the signer funds it with what the venue charges and gets the rest back
+    #[account(
+        mut,
+        seeds = [b"pool_creator", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub pool_creator: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Launch token, spl token or token 2022. Always paired with WSOL, whichever sorts first
+    #[account(
+        mut,
+        constraint = token_mint.key() == token_state.mint.key() @NottyTerminalError::WrongMint,
+        constraint = token_mint.key() != wsol_mint.key() @NottyTerminalError::InvalidTokenOrdering,
+        mint::token_program = launch_token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"token_state", token_mint.key().as_ref()
+        ],
+        bump = token_state.bump,
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    #[account(
+        mut,
+        constraint = token_vault.owner == token_state.key(),
+        constraint = token_vault.mint == token_mint.key() @NottyTerminalError::WrongMint,
+        token::token_program = launch_token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        address = spl_token::native_mint::ID,
+        mint::token_program = token_program,
+    )]
+    pub wsol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = token_state,
+        associated_token::token_program = token_program)]
+    pub vault_wsol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Launch history of the creator, created here for tokens launched before profiles existed
+    #[account(
+        init_if_needed,
+        seeds = [b"creator_profile", creator.key().as_ref()],
+        bump,
+        space = 8 + CreatorProfile::INIT_SPACE,
+        payer = signer
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+
+    /// pool creator account the launch tokens are staged in before the venue pulls them
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = token_mint,
+        associated_token::authority = pool_creator,
+        associated_token::token_program = launch_token_program,
+    )]
+    pub pool_creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// pool creator account the wrapped SOL is staged in before the venue pulls it
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = pool_creator,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_creator_wsol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Program of the WSOL side
+    pub token_program: Program<'info, Token>,
+    /// Program of the launch token, spl token or token program 2022
+    pub launch_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Implemented by the accounts struct of every venue a curve can graduate into.
+/// The venue only has to create its pool, validation, staging of the liquidity
+/// and the final state updates are shared through `MigrationCommon`.
+pub trait MigrationAdapter<'info> {
+    const VENUE: MigrationVenue;
+
+    /// Lamports the venue can charge the pool creator (create pool fee and rent of the
+    /// pool accounts), with headroom. Lent by the signer for the pool creation
+    const POOL_CREATION_BUDGET: u64;
+
+    type Params;
+
+    fn common(&mut self) -> &mut MigrationCommon<'info>;
+
+    /// Creates the pool out of the staged pool creator accounts, hands the position to
+    /// the creator and returns the pool address
+    fn create_pool(
+        &mut self,
+        token_amount: u64,
+        sol_amount: u64,
+        params: Self::Params,
+        bumps: &MigrationCommonBumps,
+    ) -> Result<Pubkey>;
+
+    /// (launch token, WSOL) vaults of the pool created by `create_pool`
+    fn pool_vaults(&self) -> (AccountInfo<'info>, AccountInfo<'info>);
+
+    /// Second half of a migration, spends exactly what `prepare_migration` checkpointed.
+    /// Any failure reverts the whole transaction so the step can simply be retried
+    fn execute_migration(
+        &mut self,
+        params: Self::Params,
+        bumps: &MigrationCommonBumps,
+    ) -> Result<()> {
+        let amounts = self
+            .common()
+            .pre_migration(Self::VENUE, Self::POOL_CREATION_BUDGET)?;
+        let pool = self.create_pool(amounts.token_amount, amounts.sol_amount, params, bumps)?;
+        let (token_vault, sol_vault) = self.pool_vaults();
+        self.common()
+            .verify_pool_price(&amounts, &token_vault, &sol_vault)?;
+        self.common().release_pool_creator(bumps.pool_creator)?;
+        self.common().post_migration(pool, &amounts, bumps)
+    }
+}
+
+impl<'info> MigrationCommon<'info> {
+    /// Checks the migration was prepared for this venue, stages the checkpointed
+    /// liquidity in the pool creator accounts and funds the pool creator with `budget`
+    pub fn pre_migration(
+        &mut self,
+        venue: MigrationVenue,
+        budget: u64,
+    ) -> Result<MigrationAmounts> {
+        self.token_state
+            .validate_migration_execute(venue, &self.global_state)?;
+
+        self.revoke_freeze_authority()?;
+
+        let amounts = self.token_state.migration_amounts;
+        self.prepare_liquidity(amounts.token_amount, amounts.sol_amount)?;
+
+        if budget > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.signer.to_account_info(),
+                        to: self.pool_creator.to_account_info(),
+                    },
+                ),
+                budget,
+            )?;
+        }
+
+        Ok(amounts)
+    }
+
+    /// Hands back what the venue didn't take: staged dust to the curve vaults, where
+    /// `finalize_migration` sweeps it, the staging account rent and the rest of the
+    /// budget to the signer
+    pub fn release_pool_creator(&mut self, pool_creator_bump: u8) -> Result<()> {
+        let mint = self.token_mint.key();
+        let pool_creator_seeds: &[&[&[u8]]] =
+            &[&[b"pool_creator", mint.as_ref(), &[pool_creator_bump]]];
+
+        self.pool_creator_token_account.reload()?;
+        self.pool_creator_wsol_account.reload()?;
+
+        let staged = [
+            (
+                &self.pool_creator_token_account,
+                &self.token_vault,
+                &self.token_mint,
+                self.launch_token_program.to_account_info(),
+            ),
+            (
+                &self.pool_creator_wsol_account,
+                &self.vault_wsol_account,
+                &self.wsol_mint,
+                self.token_program.to_account_info(),
+            ),
+        ];
+        for (staging, vault, mint, token_program) in staged {
+            if staging.amount > 0 {
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.clone(),
+                        token_interface::TransferChecked {
+                            from: staging.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: vault.to_account_info(),
+                            authority: self.pool_creator.to_account_info(),
+                        },
+                        pool_creator_seeds,
+                    ),
+                    staging.amount,
+                    mint.decimals,
+                )?;
+            }
+
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program,
+                token_interface::CloseAccount {
+                    account: staging.to_account_info(),
+                    destination: self.signer.to_account_info(),
+                    authority: self.pool_creator.to_account_info(),
+                },
+                pool_creator_seeds,
+            ))?;
+        }
+
+        let lamports = self.pool_creator.lamports();
+        if lamports > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.pool_creator.to_account_info(),
+                        to: self.signer.to_account_info(),
+                    },
+                    pool_creator_seeds,
+                ),
+                lamports,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Moves the whole position the pool creator received (LP tokens or position NFT)
+    /// to the creator's associated token account and closes the pool creator's
+    pub fn hand_over_position(
+        &self,
+        mint: &AccountInfo<'info>,
+        pool_creator_account: &AccountInfo<'info>,
+        creator_account: &AccountInfo<'info>,
+        token_program: &AccountInfo<'info>,
+        pool_creator_bump: u8,
+    ) -> Result<()> {
+        let token_mint = self.token_mint.key();
+        let pool_creator_seeds: &[&[&[u8]]] =
+            &[&[b"pool_creator", token_mint.as_ref(), &[pool_creator_bump]]];
+
+        associated_token::create_idempotent(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: self.signer.to_account_info(),
+                associated_token: creator_account.clone(),
+                authority: self.creator.to_account_info(),
+                mint: mint.clone(),
+                system_program: self.system_program.to_account_info(),
+                token_program: token_program.clone(),
+            },
+        ))?;
+
+        let decimals = {
+            let data = mint.try_borrow_data()?;
+            Mint::try_deserialize(&mut &data[..])?.decimals
+        };
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                token_interface::TransferChecked {
+                    from: pool_creator_account.clone(),
+                    mint: mint.clone(),
+                    to: creator_account.clone(),
+                    authority: self.pool_creator.to_account_info(),
+                },
+                pool_creator_seeds,
+            ),
+            vault_balance(pool_creator_account)?,
+            decimals,
+        )?;
+
+        token_interface::close_account(CpiContext::new_with_signer(
+            token_program.clone(),
+            token_interface::CloseAccount {
+                account: pool_creator_account.clone(),
+                destination: self.signer.to_account_info(),
+                authority: self.pool_creator.to_account_info(),
+            },
+            pool_creator_seeds,
+        ))
+    }
+
+    /// The pool has to open at the final curve price, checked against what actually
+    /// landed in its vaults
+    pub fn verify_pool_price(
+        &self,
+        amounts: &MigrationAmounts,
+        token_vault: &AccountInfo<'info>,
+        sol_vault: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let pool_tokens = vault_balance(token_vault)?;
+        let pool_sol = vault_balance(sol_vault)?;
+
+        amounts.verify_price_continuity(self.token_state.to_curve_units(pool_tokens)?, pool_sol)
+    }
+
+    /// Marks the curve as migrated once the venue created its pool
+    pub fn post_migration(
+        &mut self,
+        pool: Pubkey,
+        amounts: &MigrationAmounts,
+        bumps: &MigrationCommonBumps,
+    ) -> Result<()> {
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        self.token_state.migrated = true;
+        self.token_state.migration_stage = MigrationStage::Completed;
+        self.token_state.migration_timestamp = timestamp;
+        self.token_state.pool = Some(pool);
+
+        // Update global metrics
+        self.global_state.total_migrations = self
+            .global_state
+            .total_migrations
+            .checked_add(1)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+
+        self.creator_profile
+            .init_if_empty(bumps.creator_profile, self.creator.key());
+        self.creator_profile
+            .record_graduation(self.token_state.sol_raised, timestamp)?;
+
+        self.token_mint.reload()?;
+
+        emit!(TokenMigrated {
+            mint: self.token_state.mint,
+            venue: self.token_state.venue,
+            pool,
+            sol_raised: self.token_state.sol_raised,
+            tokens_sold: self.token_state.tokens_sold,
+            token_amount: amounts.token_amount,
+            sol_amount: amounts.sol_amount,
+            tokens_burned: amounts.tokens_burned,
+            price_per_million: amounts.price_per_million,
+            creator: self.token_state.creator,
+            timestamp,
+            mint_authority: self.token_mint.mint_authority.into(),
+            freeze_authority: self.token_mint.freeze_authority.into(),
+        });
+
+        Ok(())
+    }
+
+    /// Nobody can freeze holders once the token trades on a venue. No-op when the
+    /// freeze authority was already revoked at creation
+    pub fn revoke_freeze_authority(&mut self) -> Result<()> {
+        if self.token_mint.freeze_authority.is_none() {
+            return Ok(());
+        }
+
+        let token_state_seeds: &[&[&[u8]]] = &[&[
+            b"token_state",
+            self.token_state.mint.as_ref(),
+            &[self.token_state.bump],
+        ]];
+
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                self.launch_token_program.to_account_info(),
+                token_interface::SetAuthority {
+                    current_authority: self.token_state.to_account_info(),
+                    account_or_mint: self.token_mint.to_account_info(),
+                },
+                token_state_seeds,
+            ),
+            token_interface::spl_token_2022::instruction::AuthorityType::FreezeAccount,
+            None,
+        )
+    }
+
+    pub fn prepare_liquidity(&mut self, token_amount: u64, sol_amount: u64) -> Result<()> {
+        let token_state_seeds: &[&[&[u8]]] = &[&[
+            b"token_state",
+            self.token_state.mint.as_ref(),
+            &[self.token_state.bump],
+        ]];
+
+        // Transfer custom tokens from token_vault to pool_creator_token_account
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.launch_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.token_vault.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.pool_creator_token_account.to_account_info(),
+                    authority: self.token_state.to_account_info(),
+                },
+                token_state_seeds,
+            ),
+            token_amount,
+            self.token_mint.decimals,
+        )?;
+
+        // Transfer WSOL from vault_wsol_account to pool_creator_wsol_account
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.vault_wsol_account.to_account_info(),
+                    mint: self.wsol_mint.to_account_info(),
+                    to: self.pool_creator_wsol_account.to_account_info(),
+                    authority: self.token_state.to_account_info(),
+                },
+                token_state_seeds,
+            ),
+            sol_amount,
+            self.wsol_mint.decimals,
+        )?;
+
+        msg!(
+            "Liquidity prepared: {} tokens, {} lamports",
+            token_amount,
+            sol_amount
+        );
+        Ok(())
+    }
+
+    /// Pools keyed by an ordered pair need token_0 < token_1, this tells on which side
+    /// the launch token lands against WSOL
+    pub fn is_token_first(&self) -> bool {
+        self.token_mint.key() < self.wsol_mint.key()
+    }
+
+    /// (token_0, token_1) mint keys in pool order
+    pub fn sorted_mint_keys(&self) -> (Pubkey, Pubkey) {
+        self.sorted(self.token_mint.key(), self.wsol_mint.key())
+    }
+
+    /// (token_0, token_1) mints in pool order
+    pub fn sorted_mints(&self) -> (AccountInfo<'info>, AccountInfo<'info>) {
+        self.sorted(
+            self.token_mint.to_account_info(),
+            self.wsol_mint.to_account_info(),
+        )
+    }
+
+    /// (token_0, token_1) staged pool creator accounts in pool order
+    pub fn sorted_pool_creator_accounts(&self) -> (AccountInfo<'info>, AccountInfo<'info>) {
+        self.sorted(
+            self.pool_creator_token_account.to_account_info(),
+            self.pool_creator_wsol_account.to_account_info(),
+        )
+    }
+
+    /// (token_0, token_1) token programs in pool order
+    pub fn sorted_token_programs(&self) -> (AccountInfo<'info>, AccountInfo<'info>) {
+        self.sorted(
+            self.launch_token_program.to_account_info(),
+            self.token_program.to_account_info(),
+        )
+    }
+
+    /// (token_0, token_1) amounts in pool order
+    pub fn sorted_amounts(&self, token_amount: u64, sol_amount: u64) -> (u64, u64) {
+        self.sorted(token_amount, sol_amount)
+    }
+
+    /// (launch token, WSOL) pair out of one in pool order, the swap undoes itself
+    pub fn unsorted<T>(&self, token_0: T, token_1: T) -> (T, T) {
+        self.sorted(token_0, token_1)
+    }
+
+    fn sorted<T>(&self, token_side: T, sol_side: T) -> (T, T) {
+        sort_pair(self.is_token_first(), token_side, sol_side)
+    }
+
+    /// Venues without token 2022 support can only take spl token launches
+    pub fn require_spl_token_launch(&self) -> Result<()> {
+        require_keys_eq!(
+            self.launch_token_program.key(),
+            Token::id(),
+            NottyTerminalError::UnsupportedTokenProgram
+        );
+        Ok(())
+    }
+}
+
+/// Token balance of a pool vault the venue program just created
+pub fn vault_balance(vault: &AccountInfo) -> Result<u64> {
+    require!(
+        *vault.owner == spl_token::ID || *vault.owner == anchor_spl::token_2022::ID,
+        NottyTerminalError::InvalidPoolAddress
+    );
+
+    let data = vault.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+/// Orders a (launch token, WSOL) pair into (token_0, token_1)
+pub fn sort_pair<T>(token_first: bool, token_side: T, sol_side: T) -> (T, T) {
+    if token_first {
+        (token_side, sol_side)
+    } else {
+        (sol_side, token_side)
+    }
+}
+
+/// sqrt(amount_b / amount_a) as a Q64.64 fixed point number, the price format used
+/// by concentrated liquidity venues
+pub fn sqrt_price_x64(amount_a: u64, amount_b: u64) -> Result<u128> {
+    require!(
+        amount_a > 0 && amount_b > 0,
+        NottyTerminalError::InvalidAmount
+    );
+
+    // scale amount_b up as far as u128 allows (keeping the shift even) before dividing
+    let shift = ((amount_b as u128).leading_zeros() - 1) & !1;
+    let ratio = ((amount_b as u128) << shift) / amount_a as u128;
+    let root = integer_sqrt_u128(ratio);
+
+    let half_shift = shift / 2;
+    let sqrt_price = if half_shift <= 64 {
+        root.checked_shl(64 - half_shift)
+            .filter(|res| res >> (64 - half_shift) == root)
+    } else {
+        Some(root >> (half_shift - 64))
+    };
+
+    sqrt_price.ok_or(error!(NottyTerminalError::NumericalOverflow))
+}
+
+/// Largest full range liquidity (Q64.64 sqrt price convention) that both amounts can
+/// cover. Finite tick bounds always need less than this, so the amounts stay sufficient
+pub fn full_range_liquidity(amount_a: u64, amount_b: u64, sqrt_price_x64: u128) -> Result<u128> {
+    require!(sqrt_price_x64 > 0, NottyTerminalError::InvalidAmount);
+
+    // amount_a = L / sqrt_price  =>  L = amount_a * sqrt_price
+    let hi = sqrt_price_x64 >> 64;
+    let lo = sqrt_price_x64 & u64::MAX as u128;
+    let liquidity_a = (amount_a as u128)
+        .checked_mul(hi)
+        .and_then(|res| res.checked_add(((amount_a as u128) * lo) >> 64))
+        .ok_or(NottyTerminalError::NumericalOverflow)?;
+
+    // amount_b = L * sqrt_price  =>  L = amount_b / sqrt_price
+    let liquidity_b = ((amount_b as u128) << 64) / sqrt_price_x64;
+
+    Ok(min(liquidity_a, liquidity_b))
+}
+
+/// First 8 bytes of sha256("global:<name>"), the instruction discriminator used by
+/// Anchor programs we CPI into without a client crate
+pub fn anchor_discriminator(name: &str) -> [u8; 8] {
+    let preimage = format!("global:{}", name);
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(preimage.as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+pub fn integer_sqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    let mut x = n;
+    let mut y = x / 2 + x % 2;
+
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+
+    x
+}
+
+#[event]
+pub struct TokenMigrated {
+    pub mint: Pubkey,
+    pub venue: MigrationVenue,
+    pub pool: Pubkey,
+    pub sol_raised: u64,
+    pub tokens_sold: u64,
+    pub token_amount: u64, // launch tokens deposited in the pool
+    pub sol_amount: u64,   // lamports deposited in the pool
+    pub tokens_burned: u64,
+    pub price_per_million: u64,
+    pub creator: Pubkey,
+    pub timestamp: i64,
+    pub mint_authority: Option<Pubkey>,
+    pub freeze_authority: Option<Pubkey>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_X64: u128 = 1 << 64;
+
+    #[test]
+    fn sqrt_price_of_simple_ratios() {
+        assert_eq!(
+            sqrt_price_x64(1_000_000_000, 1_000_000_000).unwrap(),
+            ONE_X64
+        );
+        assert_eq!(sqrt_price_x64(1, 4).unwrap(), 2 * ONE_X64);
+        assert_eq!(sqrt_price_x64(4, 1).unwrap(), ONE_X64 / 2);
+        assert_eq!(sqrt_price_x64(1, u64::MAX).unwrap() >> 64, (1 << 32) - 1);
+    }
+
+    #[test]
+    fn sqrt_price_of_a_graduated_curve() {
+        // 200M tokens (9 decimals) against 450 SOL
+        let token_amount = 200_000_000_000_000_000;
+        let sol_amount = 450_000_000_000;
+        let sqrt_price = sqrt_price_x64(token_amount, sol_amount).unwrap();
+
+        // squaring it back gives the lamports per base unit within rounding
+        let implied = sqrt_price * sqrt_price / ONE_X64 * token_amount as u128 / ONE_X64;
+        assert!(implied.abs_diff(sol_amount as u128) <= sol_amount as u128 / 1_000_000);
+    }
+
+    #[test]
+    fn sqrt_price_rejects_empty_sides() {
+        let err = sqrt_price_x64(0, 1).unwrap_err();
+        assert_eq!(err, NottyTerminalError::InvalidAmount.into());
+        let err = sqrt_price_x64(1, 0).unwrap_err();
+        assert_eq!(err, NottyTerminalError::InvalidAmount.into());
+    }
+
+    #[test]
+    fn full_range_liquidity_at_the_pool_price() {
+        let sqrt_price = sqrt_price_x64(1_000_000_000, 4_000_000_000).unwrap();
+        assert_eq!(
+            full_range_liquidity(1_000_000_000, 4_000_000_000, sqrt_price).unwrap(),
+            2_000_000_000
+        );
+    }
+
+    #[test]
+    fn full_range_liquidity_is_capped_by_the_short_side() {
+        // at price 4 the b side only covers 1e9 / 2
+        assert_eq!(
+            full_range_liquidity(1_000_000_000, 1_000_000_000, 2 * ONE_X64).unwrap(),
+            500_000_000
+        );
+        // at price 1/4 the a side only covers 1e9 / 2
+        assert_eq!(
+            full_range_liquidity(1_000_000_000, 1_000_000_000, ONE_X64 / 2).unwrap(),
+            500_000_000
+        );
+    }
+
+    #[test]
+    fn full_range_liquidity_rejects_a_zero_price() {
+        let err = full_range_liquidity(1, 1, 0).unwrap_err();
+        assert_eq!(err, NottyTerminalError::InvalidAmount.into());
+    }
+
+    #[test]
+    fn sorted_amounts_follow_the_mint_order() {
+        assert_eq!(sort_pair(true, 10u64, 20u64), (10, 20));
+        assert_eq!(sort_pair(false, 10u64, 20u64), (20, 10));
+    }
+
+    #[test]
+    fn anchor_discriminators_match_the_venue_idls() {
+        assert_eq!(
+            anchor_discriminator("initialize_pool"),
+            [95, 180, 10, 172, 84, 174, 232, 40]
+        );
+        assert_eq!(
+            anchor_discriminator("open_position"),
+            [135, 128, 47, 77, 15, 152, 240, 49]
+        );
+        assert_eq!(
+            anchor_discriminator("increase_liquidity"),
+            [46, 156, 243, 118, 13, 205, 251, 178]
+        );
+    }
+}
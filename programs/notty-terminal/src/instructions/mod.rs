@@ -1,9 +1,53 @@
+pub mod check_migration;
+pub mod claim_vested;
 pub mod create_token;
+pub mod create_token_2022;
+pub mod create_token_pda;
+pub mod emergency_unwind;
+pub mod finalize_migration;
 pub mod initialize_global_state;
 pub mod launch;
+pub mod launch_meteora_damm;
+pub mod launch_orca_whirlpool;
+pub mod launch_raydium_amm;
+pub mod migrate_global_state;
+pub mod migrate_token_state;
+pub mod migration_common;
+pub mod open_refunds;
+pub mod prepare_migration;
 pub mod purchase_token;
+pub mod redeem_refund;
+pub mod reserve_symbol;
+pub mod set_token_profile;
+pub mod set_verification;
+pub mod token_launch;
+pub mod token_metadata;
+pub mod update_global_state;
+pub mod update_token_metadata;
 
+pub use check_migration::*;
+pub use claim_vested::*;
 pub use create_token::*;
+pub use create_token_2022::*;
+pub use create_token_pda::*;
+pub use emergency_unwind::*;
+pub use finalize_migration::*;
 pub use initialize_global_state::*;
 pub use launch::*;
+pub use launch_meteora_damm::*;
+pub use launch_orca_whirlpool::*;
+pub use launch_raydium_amm::*;
+pub use migrate_global_state::*;
+pub use migrate_token_state::*;
+pub use migration_common::*;
+pub use open_refunds::*;
+pub use prepare_migration::*;
 pub use purchase_token::*;
+pub use redeem_refund::*;
+pub use reserve_symbol::*;
+pub use set_token_profile::*;
+pub use set_verification::*;
+pub use token_launch::*;
+pub use token_metadata::*;
+pub use update_global_state::*;
+pub use update_token_metadata::*;
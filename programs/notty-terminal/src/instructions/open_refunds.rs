@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{close_account, spl_token, CloseAccount, Token},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{error::NottyTerminalError, MigrationStage, RefundReason, TokenState};
+
+/// Puts a curve that missed its graduation deadline into refund. Trading and migration
+/// stop for good and the sol vault balance is split between the holders. Can be called by
+/// anyone, also on a prepared migration nobody managed to execute in time
+#[derive(Accounts)]
+pub struct OpenRefunds<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = token_mint.key() == token_state.mint.key() @NottyTerminalError::WrongMint,
+        mint::token_program = launch_token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"token_state", token_mint.key().as_ref()
+        ],
+        bump = token_state.bump,
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    #[account(
+        constraint = token_vault.owner == token_state.key(),
+        constraint = token_vault.mint == token_mint.key() @NottyTerminalError::WrongMint,
+        token::token_program = launch_token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", token_vault.key().as_ref()],
+        bump = token_state.sol_vault_bump,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        address = spl_token::native_mint::ID,
+        mint::token_program = token_program,
+    )]
+    pub wsol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Curve WSOL account, required to refund a prepared migration so its SOL is refunded too
+    #[account(
+        mut,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = token_state,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_wsol_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Program of the WSOL side
+    pub token_program: Program<'info, Token>,
+    pub launch_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> OpenRefunds<'info> {
+    pub fn handle_open_refunds(&mut self) -> Result<()> {
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.token_state.is_past_graduation_deadline(timestamp),
+            NottyTerminalError::GraduationDeadlineNotReached
+        );
+
+        match self.token_state.migration_stage {
+            MigrationStage::Trading => {}
+            // the raised SOL is wrapped, bring it back to the sol vault first
+            MigrationStage::Prepared => self.unwrap_sol()?,
+            _ => return err!(NottyTerminalError::TradingLocked),
+        }
+
+        // the vault keeps its rent exemption, everything above goes back to the holders
+        let lamports = self
+            .sol_vault
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        let refund_pool =
+            self.token_state
+                .open_refunds(RefundReason::GraduationDeadline, lamports, timestamp);
+
+        emit!(RefundsOpened {
+            mint: self.token_mint.key(),
+            lamports: refund_pool.lamports,
+            tokens_outstanding: refund_pool.tokens_outstanding,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn unwrap_sol(&mut self) -> Result<()> {
+        let vault_wsol_account = self
+            .vault_wsol_account
+            .as_ref()
+            .ok_or(NottyTerminalError::InsufficientVaultBalance)?;
+
+        let token_state_seeds: &[&[&[u8]]] = &[&[
+            b"token_state",
+            self.token_state.mint.as_ref(),
+            &[self.token_state.bump],
+        ]];
+
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: vault_wsol_account.to_account_info(),
+                destination: self.sol_vault.to_account_info(),
+                authority: self.token_state.to_account_info(),
+            },
+            token_state_seeds,
+        ))
+    }
+}
+
+#[event]
+pub struct RefundsOpened {
+    pub mint: Pubkey,
+    pub lamports: u64,
+    pub tokens_outstanding: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,246 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::Metadata,
+    token::{self, spl_token, Token},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+use super::token_metadata::LaunchMetadata;
+use crate::{error::NottyTerminalError, GlobalState, MigrationStage, MigrationVenue, TokenState};
+
+/// First half of a migration: collects the fee, burns the excess supply, wraps the raised
+/// SOL and locks trading and metadata. The liquidity stays in the program owned curve vaults until
+/// the venue specific `execute_migration` creates the pool, which can be retried freely.
+#[derive(Accounts)]
+pub struct PrepareMigration<'info> {
+    /// Address paying the migration fee and any rent. Can be anyone
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        constraint = platform_sol_vault.key() == global_state.vault.key() @NottyTerminalError::WrongVault
+    )]
+    pub platform_sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == token_state.mint.key() @NottyTerminalError::WrongMint,
+        mint::token_program = launch_token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"token_state", token_mint.key().as_ref()
+        ],
+        bump = token_state.bump,
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    #[account(
+        mut,
+        constraint = token_vault.owner == token_state.key(),
+        constraint = token_vault.mint == token_mint.key() @NottyTerminalError::WrongMint,
+        token::token_program = launch_token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", token_vault.key().as_ref()],
+        bump = token_state.sol_vault_bump,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        address = spl_token::native_mint::ID,
+        mint::token_program = token_program,
+    )]
+    pub wsol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = token_state,
+        associated_token::token_program = token_program)]
+    pub vault_wsol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    /// CHECK: Metaplex metadata of the mint, empty for Token-2022 launches
+    pub metadata_account: UncheckedAccount<'info>,
+
+    /// Program of the WSOL side
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    /// Program of the launch token, spl token or token program 2022
+    pub launch_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PrepareMigration<'info> {
+    pub fn handle_prepare_migration(&mut self) -> Result<()> {
+        let amounts = self.token_state.validate_migration_prepare(
+            &self.global_state,
+            &self.launch_token_program.key(),
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        // collect Notty Migration Fee
+        let migration_fee = self.global_state.migration_fee_lamport;
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.signer.to_account_info(),
+                    to: self.platform_sol_vault.to_account_info(),
+                },
+            ),
+            migration_fee,
+        )?;
+
+        // Step 1: Burn what the pool can't take at the curve price
+        self.burn_excess_tokens(amounts.tokens_burned)?;
+        // Step 2: Wrap the SOL the pool is seeded with
+        self.wrap_sol(amounts.sol_amount)?;
+
+        // Step 3: Metadata can't change once the token leaves the curve
+        self.lock_metadata()?;
+
+        // Step 4: Checkpoint, execute_migration only ever spends these amounts
+        self.token_state.migration_stage = MigrationStage::Prepared;
+        self.token_state.migration_amounts = amounts;
+
+        emit!(MigrationPrepared {
+            mint: self.token_state.mint,
+            venue: self.token_state.venue,
+            token_amount: amounts.token_amount,
+            sol_amount: amounts.sol_amount,
+            tokens_burned: amounts.tokens_burned,
+            price_per_million: amounts.price_per_million,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn lock_metadata(&mut self) -> Result<()> {
+        let token_state_seeds: &[&[&[u8]]] = &[&[
+            b"token_state",
+            self.token_state.mint.as_ref(),
+            &[self.token_state.bump],
+        ]];
+
+        LaunchMetadata {
+            mint: self.token_mint.to_account_info(),
+            token_state: self.token_state.to_account_info(),
+            launch_token_program: self.launch_token_program.to_account_info(),
+            metadata_account: self.metadata_account.to_account_info(),
+            token_metadata_program: self.token_metadata_program.to_account_info(),
+            signer_seeds: token_state_seeds,
+        }
+        .lock()?;
+
+        emit!(TokenMetadataLocked {
+            mint: self.token_state.mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn burn_excess_tokens(&mut self, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let token_state_seeds: &[&[&[u8]]] = &[&[
+            b"token_state",
+            self.token_state.mint.as_ref(),
+            &[self.token_state.bump],
+        ]];
+
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                self.launch_token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: self.token_mint.to_account_info(),
+                    from: self.token_vault.to_account_info(),
+                    authority: self.token_state.to_account_info(),
+                },
+                token_state_seeds,
+            ),
+            amount,
+        )
+    }
+
+    pub fn wrap_sol(&mut self, amount: u64) -> Result<()> {
+        // transfer sol to token account
+        let token_vault = self.token_vault.key();
+
+        let sol_vault_seeds: &[&[&[u8]]] = &[&[
+            b"sol_vault",
+            token_vault.as_ref(), // Use token_vault.key() to match CreateToken!
+            &[self.token_state.sol_vault_bump],
+        ]];
+
+        let cpi_context = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            Transfer {
+                from: self.sol_vault.to_account_info(),
+                to: self.vault_wsol_account.to_account_info(),
+            },
+            sol_vault_seeds,
+        );
+
+        transfer(cpi_context, amount)?;
+
+        // Sync the native token to reflect the new SOL balance as wSOL
+        let cpi_accounts = token::SyncNative {
+            account: self.vault_wsol_account.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::sync_native(cpi_ctx)?;
+        Ok(())
+    }
+}
+
+#[event]
+pub struct MigrationPrepared {
+    pub mint: Pubkey,
+    pub venue: MigrationVenue,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub tokens_burned: u64,
+    pub price_per_million: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenMetadataLocked {
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
@@ -8,15 +8,9 @@ use anchor_spl::{
 };
 
 use crate::{
-    error::{NottyTerminalError, PriceCalculationError},
-    GlobalState, TokenState,
+    error::NottyTerminalError, AllowlistProof, AllowlistPurchase, GlobalState, TokenState,
 };
 
-use std::cmp::min;
-
-pub const INITIAL_MCAP_SOL: u64 = 50;
-pub const MIGRATION_MCAP_SOL: u64 = 450;
-pub const TOTAL_SUPPLY: u64 = 1_000_000_000; // 1B tokens
 pub const MIGRATION_THRESHOLD_PCT: u64 = 86; // Migration at 86% sold
 
 #[derive(Accounts)]
@@ -26,9 +20,8 @@ pub struct TokenInteraction<'info> {
 
     #[account(
         mut,
-        constraint = creator_mint.mint_authority == Some(token_state.key()).into(),
-        constraint = creator_mint.freeze_authority == Some(token_state.key()).into(),
-        constraint = creator_mint.key() == token_state.mint.key()
+        constraint = creator_mint.key() == token_state.mint.key(),
+        mint::token_program = token_program,
     )]
     pub creator_mint: InterfaceAccount<'info, Mint>,
 
@@ -36,7 +29,8 @@ pub struct TokenInteraction<'info> {
         init_if_needed,
         payer = user,
         associated_token::mint = creator_mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
     pub user_ata: InterfaceAccount<'info, TokenAccount>,
 
@@ -44,6 +38,7 @@ pub struct TokenInteraction<'info> {
         mut,
         constraint =  token_vault.mint == creator_mint.key(),
         constraint = token_vault.owner == token_state.key(),
+        token::token_program = token_program,
     )]
     pub token_vault: InterfaceAccount<'info, TokenAccount>,
 
@@ -78,52 +73,68 @@ pub struct TokenInteraction<'info> {
     )]
     pub global_state: Account<'info, GlobalState>,
 
+    /// Tracks what the buyer purchased, required during the allowlist phase and rejected after
+    /// it. Sells never take it
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"allowlist_purchase", creator_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = 8 + AllowlistPurchase::INIT_SPACE,
+    )]
+    pub allowlist_purchase: Option<Box<Account<'info, AllowlistPurchase>>>,
+
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 impl<'info> TokenInteraction<'info> {
-    pub fn handle_purchase(&mut self, args: PurchaseTokenArgs) -> Result<()> {
+    pub fn handle_purchase(
+        &mut self,
+        args: PurchaseTokenArgs,
+        allowlist_purchase_bump: Option<u8>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
         require!(
             !self.token_state.migrated,
             NottyTerminalError::AlreadyGraduated
         );
+        require!(
+            self.token_state.is_trading_open(),
+            NottyTerminalError::TradingLocked
+        );
+        require!(
+            self.token_state.has_trading_started(now),
+            NottyTerminalError::TradingNotStarted
+        );
+        require!(
+            !self.token_state.is_past_graduation_deadline(now),
+            NottyTerminalError::GraduationDeadlinePassed
+        );
 
         let amount = args.amount;
 
-        // Calculate base cost without fees
-        let base_cost_lamports = self.get_current_token_price(amount)?;
-
-        // Calculate trading fee (1.5%)
-        let trading_fee = base_cost_lamports
-            .checked_mul(self.global_state.trading_fee_bps as u64)
-            .and_then(|f| f.checked_div(10000))
-            .ok_or(NottyTerminalError::NumericalOverflow)?;
-
-        // Total cost = base + fee
-        let total_cost_lamports = base_cost_lamports
-            .checked_add(trading_fee)
-            .ok_or(NottyTerminalError::NumericalOverflow)?;
-
-        // Check slippage against total cost
+        if self.token_state.is_allowlist_phase(now) {
+            self.record_allowlist_purchase(
+                args.allowlist_proof.as_ref(),
+                amount,
+                allowlist_purchase_bump,
+            )?;
+        } else {
+            // would otherwise be created, and paid for, for nothing
+            require!(
+                self.allowlist_purchase.is_none(),
+                NottyTerminalError::AllowlistPhaseOver
+            );
+        }
+
+        let quote = PurchaseQuote::new(&self.token_state, &self.global_state, amount)?;
         require!(
-            total_cost_lamports <= args.max_sol_cost,
+            quote.total_cost <= args.max_sol_cost,
             NottyTerminalError::SlippageExceeded
         );
 
-        // Transfer total cost from buyer
-        let transfer_cpi_account = Transfer {
-            from: self.user.to_account_info(),
-            to: self.sol_vault.to_account_info(),
-        };
-
-        transfer(
-            CpiContext::new(self.system_program.to_account_info(), transfer_cpi_account),
-            total_cost_lamports,
-        )?;
-
-        // Transfer trading fee to platform vault
         let token_vault = self.token_vault.key();
         let sol_vault_seeds: &[&[&[u8]]] = &[&[
             b"sol_vault",
@@ -131,18 +142,13 @@ impl<'info> TokenInteraction<'info> {
             &[self.token_state.sol_vault_bump],
         ]];
 
-        let fee_transfer = Transfer {
-            from: self.sol_vault.to_account_info(),
-            to: self.platform_sol_vault.to_account_info(), // Platform vault
-        };
-
-        transfer(
-            CpiContext::new_with_signer(
-                self.system_program.to_account_info(),
-                fee_transfer,
-                sol_vault_seeds,
-            ),
-            trading_fee,
+        pay_for_purchase(
+            &quote,
+            self.user.to_account_info(),
+            self.sol_vault.to_account_info(),
+            self.platform_sol_vault.to_account_info(),
+            self.system_program.to_account_info(),
+            sol_vault_seeds,
         )?;
 
         // Transfer tokens to buyer
@@ -168,55 +174,75 @@ impl<'info> TokenInteraction<'info> {
             self.creator_mint.decimals,
         )?;
 
-        // Update state (only track base amount for bonding curve)
-        self.token_state.tokens_sold = self
-            .token_state
-            .tokens_sold
-            .checked_add(amount)
-            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        record_purchase(
+            &mut self.token_state,
+            &mut self.global_state,
+            &quote,
+            amount,
+            self.user.key(),
+        )?;
 
-        self.token_state.sol_raised = self
-            .token_state
-            .sol_raised
-            .checked_add(base_cost_lamports) // Only base cost counted toward migration
-            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        Ok(())
+    }
 
-        // Update global metrics
-        self.global_state.total_fees_collected = self
-            .global_state
-            .total_fees_collected
-            .checked_add(trading_fee)
-            .ok_or(NottyTerminalError::NumericalOverflow)?;
+    /// Checks the buyer proof against the token root and counts `amount` toward their allocation
+    fn record_allowlist_purchase(
+        &mut self,
+        proof: Option<&AllowlistProof>,
+        amount: u64,
+        bump: Option<u8>,
+    ) -> Result<()> {
+        let (Some(proof), Some(allowlist_purchase), Some(bump)) =
+            (proof, self.allowlist_purchase.as_mut(), bump)
+        else {
+            return err!(NottyTerminalError::MissingAllowlistProof);
+        };
 
-        self.global_state.total_trading_volume = self
-            .global_state
-            .total_trading_volume
-            .checked_add(total_cost_lamports)
-            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        proof.verify(&self.token_state.allowlist_root, &self.user.key())?;
 
-        emit!(PurchasedToken {
-            amount_purchased: amount,
-            base_cost: base_cost_lamports,
-            trading_fee,
-            total_cost: total_cost_lamports,
-            current_price: self.get_current_token_price(1_000_000_000)?,
-            migrated: self.token_state.migrated,
-            mint: self.token_state.mint,
-            sol_raised: self.token_state.sol_raised,
-            tokens_sold: self.token_state.tokens_sold,
-            total_supply: self.token_state.total_supply,
-            buyer: self.user.key(),
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+        if allowlist_purchase.user == Pubkey::default() {
+            allowlist_purchase.bump = bump;
+            allowlist_purchase.mint = self.creator_mint.key();
+            allowlist_purchase.user = self.user.key();
+        }
+
+        let purchased = allowlist_purchase
+            .purchased
+            .checked_add(amount)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        require!(
+            proof.allocation == 0 || purchased <= proof.allocation,
+            NottyTerminalError::AllowlistAllocationExceeded
+        );
+        allowlist_purchase.purchased = purchased;
 
         Ok(())
     }
 
     pub fn handle_sell(&mut self, args: SellTokenArgs) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
         require!(
             !self.token_state.migrated,
             NottyTerminalError::AlreadyGraduated
         );
+        require!(
+            self.token_state.is_trading_open(),
+            NottyTerminalError::TradingLocked
+        );
+        require!(
+            self.token_state.has_trading_started(now),
+            NottyTerminalError::TradingNotStarted
+        );
+        require!(
+            !self.token_state.is_past_graduation_deadline(now),
+            NottyTerminalError::GraduationDeadlinePassed
+        );
+
+        // init_if_needed would otherwise create it on a sell
+        require!(
+            self.allowlist_purchase.is_none(),
+            NottyTerminalError::UnexpectedAllowlistPurchase
+        );
 
         let amount = args.amount;
 
@@ -230,10 +256,7 @@ impl<'info> TokenInteraction<'info> {
         let base_proceeds = self.get_current_sell_price(amount)?;
 
         // Calculate trading fee (1.5% of proceeds)
-        let trading_fee = base_proceeds
-            .checked_mul(self.global_state.trading_fee_bps as u64)
-            .and_then(|f| f.checked_div(10000))
-            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        let trading_fee = self.global_state.trading_fee(base_proceeds)?;
 
         // Net proceeds after fee
         let net_proceeds = base_proceeds
@@ -302,17 +325,7 @@ impl<'info> TokenInteraction<'info> {
         )?;
 
         // Update state
-        self.token_state.tokens_sold = self
-            .token_state
-            .tokens_sold
-            .checked_sub(amount)
-            .ok_or(NottyTerminalError::NumericalOverflow)?;
-
-        self.token_state.sol_raised = self
-            .token_state
-            .sol_raised
-            .checked_sub(base_proceeds)
-            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        self.token_state.record_sale(amount, base_proceeds)?;
 
         // Update global metrics
         self.global_state.total_fees_collected = self
@@ -332,7 +345,8 @@ impl<'info> TokenInteraction<'info> {
             base_proceeds,
             trading_fee,
             net_proceeds,
-            current_price: self.get_current_token_price(1_000_000_000)?,
+            current_price: self
+                .get_current_token_price(self.token_state.base_units_per_token()?)?,
             migrated: self.token_state.migrated,
             mint: self.token_state.mint,
             sol_raised: self.token_state.sol_raised,
@@ -345,97 +359,116 @@ impl<'info> TokenInteraction<'info> {
         Ok(())
     }
 
-    pub fn calculate_current_market_cap(&self) -> Result<u64> {
-        const BASE_PRICE_PER_MILLION: u64 = 50;
-        const MAX_PRICE_PER_MILLION: u64 = 450;
-        const PRICE_RANGE: u64 = MAX_PRICE_PER_MILLION - BASE_PRICE_PER_MILLION;
-
-        let total_base_units = TOTAL_SUPPLY * 1_000_000_000;
-        let migration_base_units = (total_base_units / 100) * MIGRATION_THRESHOLD_PCT;
-
-        let progress = (self.token_state.tokens_sold * 1000) / migration_base_units;
-        let capped_progress = min(progress, 1000);
-        let sqrt_progress = integer_sqrt(capped_progress)?;
-
-        let price_per_million = BASE_PRICE_PER_MILLION + (PRICE_RANGE * sqrt_progress / 31);
-        let total_millions = total_base_units / 1_000_000;
-        let market_cap_lamports = price_per_million * total_millions;
-
-        Ok(market_cap_lamports)
-    }
-
     pub fn get_current_sell_price(&self, amount_base_units: u64) -> Result<u64> {
-        const BASE_PRICE_PER_MILLION: u64 = 50;
-        const MAX_PRICE_PER_MILLION: u64 = 450;
-        const PRICE_RANGE: u64 = MAX_PRICE_PER_MILLION - BASE_PRICE_PER_MILLION;
-
-        let new_tokens_sold = self
-            .token_state
-            .tokens_sold
-            .checked_sub(amount_base_units)
-            .ok_or(NottyTerminalError::InsufficientTokensSold)?;
-
-        let total_base_units = TOTAL_SUPPLY * 1_000_000_000;
-        // Fix overflow here too
-        let migration_base_units = (total_base_units / 100) * MIGRATION_THRESHOLD_PCT;
-
-        let new_progress = (new_tokens_sold * 1000) / migration_base_units;
-        let new_sqrt = integer_sqrt(min(new_progress, 1000))?;
-        let new_price = BASE_PRICE_PER_MILLION + (PRICE_RANGE * new_sqrt / 31);
-
-        let current_progress = (self.token_state.tokens_sold * 1000) / migration_base_units;
-        let current_sqrt = integer_sqrt(min(current_progress, 1000))?;
-        let current_price = BASE_PRICE_PER_MILLION + (PRICE_RANGE * current_sqrt / 31);
-
-        let avg_price_per_million = (current_price + new_price) / 2;
-        let sell_proceeds = (amount_base_units / 1_000_000) * avg_price_per_million;
-
-        Ok(sell_proceeds)
+        self.token_state.sale_proceeds(amount_base_units)
     }
 
-    // pub fn get_current_token_price(&self, amount_base_units: u64) -> Result<u64> {
-    //     const BASE_PRICE_PER_MILLION: u64 = 50;
-    //     const MAX_PRICE_PER_MILLION: u64 = 450;
-    //     const PRICE_RANGE: u64 = MAX_PRICE_PER_MILLION - BASE_PRICE_PER_MILLION;
-
-    //     // Avoid overflow by dividing first
-    //     let total_base_units = TOTAL_SUPPLY * 1_000_000_000;
-    //     // Instead of: total_base_units * 86 / 100
-    //     // Do: (total_base_units / 100) * 86
-    //     let migration_base_units = (total_base_units / 100) * MIGRATION_THRESHOLD_PCT;
-
-    //     let progress = (self.token_state.tokens_sold * 1000) / migration_base_units;
-    //     let capped_progress = min(progress, 1000);
-    //     let sqrt_progress = integer_sqrt(capped_progress)?;
-
-    //     let price_per_million = BASE_PRICE_PER_MILLION + (PRICE_RANGE * sqrt_progress / 31);
-    //     let total_cost = (amount_base_units / 1_000_000) * price_per_million;
-
-    //     Ok(total_cost)
-    // }
     pub fn get_current_token_price(&self, amount_base_units: u64) -> Result<u64> {
-        const BASE_PRICE_PER_MILLION: u64 = 50;
-        const MAX_PRICE_PER_MILLION: u64 = 450;
-        const PRICE_RANGE: u64 = MAX_PRICE_PER_MILLION - BASE_PRICE_PER_MILLION;
-
-        // Scale to make progress more sensitive
-        // Using basis points (10000 = 100%) for better precision
-        let total_base_units = TOTAL_SUPPLY * 1_000_000_000;
-        let migration_base_units = (total_base_units / 100) * MIGRATION_THRESHOLD_PCT;
+        self.token_state.purchase_cost(amount_base_units)
+    }
+}
 
-        // Calculate progress in basis points (10000 = 100%)
-        let progress_bps = (self.token_state.tokens_sold * 10000) / migration_base_units;
+/// What buying an amount off the curve costs, shared by `purchase_token` and the dev-buy
+pub struct PurchaseQuote {
+    pub base_cost: u64, // counted toward migration
+    pub trading_fee: u64,
+    pub total_cost: u64,
+}
 
-        // Square root of basis points (0-10000 becomes 0-100)
-        let sqrt_progress = integer_sqrt(min(progress_bps, 10000))?;
+impl PurchaseQuote {
+    pub fn new(token_state: &TokenState, global_state: &GlobalState, amount: u64) -> Result<Self> {
+        let base_cost = token_state.purchase_cost(amount)?;
+        let trading_fee = global_state.trading_fee(base_cost)?;
+        let total_cost = base_cost
+            .checked_add(trading_fee)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
 
-        // Price increases from base to max (sqrt of 10000 = 100)
-        let price_per_million = BASE_PRICE_PER_MILLION + (PRICE_RANGE * sqrt_progress / 100);
+        Ok(Self {
+            base_cost,
+            trading_fee,
+            total_cost,
+        })
+    }
+}
 
-        let total_cost = (amount_base_units / 1_000_000) * price_per_million;
+/// Charges the buyer the total cost into the sol vault, which passes the trading fee
+/// on to the platform vault
+pub fn pay_for_purchase<'info>(
+    quote: &PurchaseQuote,
+    buyer: AccountInfo<'info>,
+    sol_vault: AccountInfo<'info>,
+    platform_vault: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    sol_vault_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    transfer(
+        CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: buyer,
+                to: sol_vault.clone(),
+            },
+        ),
+        quote.total_cost,
+    )?;
+
+    transfer(
+        CpiContext::new_with_signer(
+            system_program,
+            Transfer {
+                from: sol_vault,
+                to: platform_vault,
+            },
+            sol_vault_seeds,
+        ),
+        quote.trading_fee,
+    )
+}
 
-        Ok(total_cost)
-    }
+/// Books a paid purchase on the curve and the platform counters
+pub fn record_purchase(
+    token_state: &mut TokenState,
+    global_state: &mut GlobalState,
+    quote: &PurchaseQuote,
+    amount: u64,
+    buyer: Pubkey,
+) -> Result<()> {
+    token_state.tokens_sold = token_state
+        .tokens_sold
+        .checked_add(amount)
+        .ok_or(NottyTerminalError::NumericalOverflow)?;
+    // only the base cost counts toward migration
+    token_state.sol_raised = token_state
+        .sol_raised
+        .checked_add(quote.base_cost)
+        .ok_or(NottyTerminalError::NumericalOverflow)?;
+    token_state.check_pool_capacity()?;
+
+    global_state.total_fees_collected = global_state
+        .total_fees_collected
+        .checked_add(quote.trading_fee)
+        .ok_or(NottyTerminalError::NumericalOverflow)?;
+    global_state.total_trading_volume = global_state
+        .total_trading_volume
+        .checked_add(quote.total_cost)
+        .ok_or(NottyTerminalError::NumericalOverflow)?;
+
+    emit!(PurchasedToken {
+        amount_purchased: amount,
+        base_cost: quote.base_cost,
+        trading_fee: quote.trading_fee,
+        total_cost: quote.total_cost,
+        current_price: token_state.purchase_cost(token_state.base_units_per_token()?)?,
+        migrated: token_state.migrated,
+        mint: token_state.mint,
+        sol_raised: token_state.sol_raised,
+        tokens_sold: token_state.tokens_sold,
+        total_supply: token_state.total_supply,
+        buyer,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
 }
 
 pub fn integer_sqrt(n: u64) -> Result<u64> {
@@ -461,6 +494,8 @@ pub fn integer_sqrt(n: u64) -> Result<u64> {
 pub struct PurchaseTokenArgs {
     pub amount: u64,
     pub max_sol_cost: u64,
+    /// required while the token is in its allowlist phase, ignored afterwards
+    pub allowlist_proof: Option<AllowlistProof>,
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
@@ -0,0 +1,140 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{error::NottyTerminalError, MigrationStage, TokenState};
+
+/// Returns curve tokens to the token vault for a pro-rata share of the refund pool
+#[derive(Accounts)]
+pub struct RedeemRefund<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        constraint = token_mint.key() == token_state.mint.key() @NottyTerminalError::WrongMint,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault.owner == token_state.key(),
+        constraint = token_vault.mint == token_mint.key() @NottyTerminalError::WrongMint,
+        token::token_program = token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"token_state", token_mint.key().as_ref()
+        ],
+        bump = token_state.bump,
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", token_vault.key().as_ref()],
+        bump = token_state.sol_vault_bump,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RedeemRefund<'info> {
+    pub fn handle_redeem_refund(&mut self, args: RedeemRefundArgs) -> Result<()> {
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.token_state.migration_stage == MigrationStage::Refunding,
+            NottyTerminalError::RefundsNotOpen
+        );
+        // emergency unwinds only pay out after the delay
+        require!(
+            self.token_state.refund_pool.is_open(timestamp),
+            NottyTerminalError::RedemptionNotOpen
+        );
+        require!(
+            self.user_ata.amount >= args.amount,
+            NottyTerminalError::InsufficientTokenBalance
+        );
+
+        let lamports = self.token_state.refund_pool.redeem(args.amount)?;
+
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    authority: self.user.to_account_info(),
+                    from: self.user_ata.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.token_vault.to_account_info(),
+                },
+            ),
+            args.amount,
+            self.token_mint.decimals,
+        )?;
+
+        let token_vault = self.token_vault.key();
+        let sol_vault_seeds: &[&[&[u8]]] = &[&[
+            b"sol_vault",
+            token_vault.as_ref(),
+            &[self.token_state.sol_vault_bump],
+        ]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.sol_vault.to_account_info(),
+                    to: self.user.to_account_info(),
+                },
+                sol_vault_seeds,
+            ),
+            lamports,
+        )?;
+
+        emit!(RefundRedeemed {
+            mint: self.token_mint.key(),
+            user: self.user.key(),
+            amount: args.amount,
+            lamports,
+            lamports_remaining: self.token_state.refund_pool.lamports,
+            tokens_outstanding: self.token_state.refund_pool.tokens_outstanding,
+            timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct RedeemRefundArgs {
+    pub amount: u64, // base units returned to the token vault
+}
+
+#[event]
+pub struct RefundRedeemed {
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub lamports: u64,
+    pub lamports_remaining: u64,
+    pub tokens_outstanding: u64,
+    pub timestamp: i64,
+}
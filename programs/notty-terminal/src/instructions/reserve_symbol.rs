@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::NottyTerminalError, GlobalState, SymbolRegistry};
+
+/// Admin reservation of a ticker ahead of its launch, so only the legitimate
+/// project can create a token with it
+#[derive(Accounts)]
+#[instruction(args: ReserveSymbolArgs)]
+pub struct ReserveSymbol<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = admin @NottyTerminalError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"symbol_registry", SymbolRegistry::seed(&args.symbol).as_ref()],
+        bump,
+        space = 8 + SymbolRegistry::INIT_SPACE,
+        payer = admin
+    )]
+    pub symbol_registry: Account<'info, SymbolRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReserveSymbol<'info> {
+    pub fn handle_reserve_symbol(
+        &mut self,
+        args: ReserveSymbolArgs,
+        bumps: &ReserveSymbolBumps,
+    ) -> Result<()> {
+        // a launched token keeps its ticker
+        require!(
+            !self.symbol_registry.is_claimed(),
+            NottyTerminalError::SymbolTaken
+        );
+        self.global_state
+            .metadata_policy
+            .validate_symbol(&args.symbol)?;
+
+        self.symbol_registry.bump = bumps.symbol_registry;
+        self.symbol_registry.symbol = args.symbol.clone();
+        self.symbol_registry.reserved_for = args.reserved_for;
+
+        emit!(SymbolReserved {
+            symbol: args.symbol,
+            reserved_for: args.reserved_for,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct ReserveSymbolArgs {
+    pub symbol: String,
+    /// creator allowed to launch with the symbol, `None` releases the reservation
+    pub reserved_for: Option<Pubkey>,
+}
+
+#[event]
+pub struct SymbolReserved {
+    pub symbol: String,
+    pub reserved_for: Option<Pubkey>,
+}
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::NottyTerminalError, TokenProfile, TokenState, MAX_PROFILE_DESCRIPTION_LENGTH,
+    MAX_PROFILE_URL_LENGTH,
+};
+
+/// Creates or replaces the profile of a token, only by its creator and while the
+/// curve is still trading
+#[derive(Accounts)]
+pub struct SetTokenProfile<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        constraint = token_mint.key() == token_state.mint.key() @NottyTerminalError::WrongMint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            b"token_state", token_mint.key().as_ref()
+        ],
+        bump = token_state.bump,
+        has_one = creator @NottyTerminalError::UnauthorizedCreator,
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"token_profile", token_mint.key().as_ref()],
+        bump,
+        space = 8 + TokenProfile::INIT_SPACE,
+        payer = creator
+    )]
+    pub token_profile: Account<'info, TokenProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetTokenProfile<'info> {
+    pub fn handle_set_token_profile(
+        &mut self,
+        args: TokenProfileArgs,
+        bumps: &SetTokenProfileBumps,
+    ) -> Result<()> {
+        require!(
+            self.token_state.is_trading_open(),
+            NottyTerminalError::MetadataLocked
+        );
+
+        write_token_profile(
+            &mut self.token_profile,
+            bumps.token_profile,
+            self.token_mint.key(),
+            self.creator.key(),
+            args,
+        )
+    }
+}
+
+/// Validates and stores a profile, shared with the launch instructions
+pub fn write_token_profile(
+    token_profile: &mut TokenProfile,
+    bump: u8,
+    mint: Pubkey,
+    creator: Pubkey,
+    args: TokenProfileArgs,
+) -> Result<()> {
+    args.validate()?;
+
+    let updated_at = Clock::get()?.unix_timestamp;
+    *token_profile = TokenProfile {
+        bump,
+        mint,
+        description: args.description.clone(),
+        website: args.website.clone(),
+        twitter: args.twitter.clone(),
+        telegram: args.telegram.clone(),
+        image_hash: args.image_hash,
+        updated_at,
+    };
+
+    emit!(TokenProfileUpdated {
+        mint,
+        creator,
+        description: args.description,
+        website: args.website,
+        twitter: args.twitter,
+        telegram: args.telegram,
+        image_hash: args.image_hash,
+        timestamp: updated_at,
+    });
+
+    Ok(())
+}
+
+/// Full profile, replaces the stored one. Links are empty or `https://` urls
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct TokenProfileArgs {
+    pub description: String,
+    pub website: String,
+    pub twitter: String,
+    pub telegram: String,
+    pub image_hash: [u8; 32],
+}
+
+impl TokenProfileArgs {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.description.len() <= MAX_PROFILE_DESCRIPTION_LENGTH,
+            NottyTerminalError::ProfileFieldTooLong
+        );
+
+        for url in [&self.website, &self.twitter, &self.telegram] {
+            require!(
+                url.len() <= MAX_PROFILE_URL_LENGTH,
+                NottyTerminalError::ProfileFieldTooLong
+            );
+            require!(
+                url.is_empty()
+                    || (url.len() > "https://".len()
+                        && url.starts_with("https://")
+                        && !url.chars().any(|c| c.is_whitespace() || c.is_control())),
+                NottyTerminalError::InvalidProfileUrl
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct TokenProfileUpdated {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub description: String,
+    pub website: String,
+    pub twitter: String,
+    pub telegram: String,
+    pub image_hash: [u8; 32],
+    pub timestamp: i64,
+}
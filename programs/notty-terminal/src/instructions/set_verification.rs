@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::NottyTerminalError, GlobalState, TokenState, Verification, VerificationKind};
+
+/// Admin marks a creator or a token as verified until `expires_at`. Reissuing with a past
+/// `expires_at` revokes the badge
+#[derive(Accounts)]
+#[instruction(args: SetVerificationArgs)]
+pub struct SetVerification<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = admin @NottyTerminalError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"verification", args.subject.as_ref()],
+        bump,
+        space = 8 + Verification::INIT_SPACE,
+        payer = admin
+    )]
+    pub verification: Account<'info, Verification>,
+
+    /// Only needed for `VerificationKind::Token`, proves the subject is a launched mint
+    #[account(
+        seeds = [b"token_state", args.subject.as_ref()],
+        bump = token_state.bump,
+    )]
+    pub token_state: Option<Account<'info, TokenState>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetVerification<'info> {
+    pub fn handle_set_verification(
+        &mut self,
+        args: SetVerificationArgs,
+        bumps: &SetVerificationBumps,
+    ) -> Result<()> {
+        require!(
+            args.kind == VerificationKind::Creator || self.token_state.is_some(),
+            NottyTerminalError::UnknownVerificationSubject
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+
+        self.verification.set_inner(Verification {
+            bump: bumps.verification,
+            subject: args.subject,
+            kind: args.kind,
+            reason_code: args.reason_code,
+            verified_by: self.admin.key(),
+            verified_at: now,
+            expires_at: args.expires_at,
+        });
+
+        emit!(VerificationUpdated {
+            subject: args.subject,
+            kind: args.kind,
+            reason_code: args.reason_code,
+            expires_at: args.expires_at,
+            active: self.verification.is_active(now),
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SetVerificationArgs {
+    pub subject: Pubkey, // creator wallet or mint
+    pub kind: VerificationKind,
+    pub reason_code: u16,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct VerificationUpdated {
+    pub subject: Pubkey,
+    pub kind: VerificationKind,
+    pub reason_code: u16,
+    pub expires_at: i64,
+    pub active: bool,
+}
@@ -0,0 +1,604 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::{
+        create, create_idempotent, get_associated_token_address_with_program_id, AssociatedToken,
+        Create,
+    },
+    token_interface::{
+        mint_to, set_authority, spl_token_2022::instruction::AuthorityType, transfer_checked,
+        MintTo, SetAuthority, TokenInterface, TransferChecked,
+    },
+};
+
+use super::{
+    create_token::{CreateTokenArgs, DevBuyLocked, TokenCreated},
+    purchase_token::{pay_for_purchase, record_purchase, PurchaseQuote},
+    set_token_profile::write_token_profile,
+};
+use crate::{
+    error::NottyTerminalError, CreatorProfile, CreatorVesting, GlobalState, MigrationAmounts,
+    MigrationStage, RefundPool, SymbolRegistry, TokenProfile, TokenState, Verification,
+    VestingSchedule, MAX_ALLOWLIST_DURATION, MAX_GRADUATION_DEADLINE, MAX_TOKEN_DECIMALS,
+    MAX_TRADING_START_DELAY, TOKEN_STATE_VERSION,
+};
+
+/// Accounts shared by every create instruction, whatever holds the metadata and however
+/// the mint address is picked. The wrapping instruction initializes the mint, the launch
+/// then registers the symbol, seeds the curve, mints the supply and runs the dev-buy
+#[derive(Accounts)]
+#[instruction(args: CreateTokenArgs)]
+pub struct TokenLaunch<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: launch mint, initialized by the wrapping instruction
+    #[account(mut)]
+    pub creator_mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        seeds = [
+            b"token_state", creator_mint.key().as_ref()
+        ],
+        bump,
+        space = 8 + TokenState::INIT_SPACE,
+        payer = creator
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+
+    /// CHECK: ATA of the token state holding the curve supply, created by the launch
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &token_state.key(),
+            &creator_mint.key(),
+            &token_program.key(),
+        ),
+    )]
+    pub token_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", token_vault.key().as_ref()],
+        bump,
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    /// CHECK: ATA of the creator, created by the launch if missing
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &creator.key(),
+            &creator_mint.key(),
+            &token_program.key(),
+        ),
+    )]
+    pub creator_ata: UncheckedAccount<'info>,
+
+    /// Only needed when the dev-buy is locked with `CreateTokenArgs::dev_buy_vesting`
+    #[account(
+        init,
+        seeds = [b"creator_vesting", creator_mint.key().as_ref()],
+        bump,
+        space = 8 + CreatorVesting::INIT_SPACE,
+        payer = creator
+    )]
+    pub creator_vesting: Option<Box<Account<'info, CreatorVesting>>>,
+
+    /// CHECK: ATA of the creator vesting account holding the locked dev-buy, created by
+    /// the launch. The associated token program rejects any other address
+    #[account(mut)]
+    pub vesting_vault: Option<UncheckedAccount<'info>>,
+
+    /// Only needed when the token is launched with `CreateTokenArgs::profile`
+    #[account(
+        init,
+        seeds = [b"token_profile", creator_mint.key().as_ref()],
+        bump,
+        space = 8 + TokenProfile::INIT_SPACE,
+        payer = creator
+    )]
+    pub token_profile: Option<Box<Account<'info, TokenProfile>>>,
+
+    /// Launch history of the creator, created on their first launch
+    #[account(
+        init_if_needed,
+        seeds = [b"creator_profile", creator.key().as_ref()],
+        bump,
+        space = 8 + CreatorProfile::INIT_SPACE,
+        payer = creator
+    )]
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+
+    /// Creator badge, an active one gets the verified listing fee
+    #[account(
+        seeds = [b"verification", creator.key().as_ref()],
+        bump = creator_verification.bump,
+    )]
+    pub creator_verification: Option<Box<Account<'info, Verification>>>,
+
+    /// Ticker registry entry, claimed by this token so nobody else can reuse the symbol
+    #[account(
+        init_if_needed,
+        seeds = [b"symbol_registry", SymbolRegistry::seed(&args.token_symbol).as_ref()],
+        bump,
+        space = 8 + SymbolRegistry::INIT_SPACE,
+        payer = creator
+    )]
+    pub symbol_registry: Box<Account<'info, SymbolRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        constraint = vault.key() == global_state.vault.key() @NottyTerminalError::WrongVault,
+        seeds = [b"vault"],
+        bump = global_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> TokenLaunch<'info> {
+    /// Validates the metadata, claims the symbol and stores the optional profile.
+    /// Runs before the metadata is written
+    pub fn register(&mut self, args: &CreateTokenArgs, bumps: &TokenLaunchBumps) -> Result<()> {
+        self.global_state.metadata_policy.validate(
+            &args.name,
+            &args.token_symbol,
+            &args.token_uri,
+        )?;
+        self.symbol_registry.claim(
+            bumps.symbol_registry,
+            &args.token_symbol,
+            self.creator_mint.key(),
+            self.creator.key(),
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        if let Some(profile) = args.profile.clone() {
+            let (Some(token_profile), Some(bump)) =
+                (self.token_profile.as_mut(), bumps.token_profile)
+            else {
+                return err!(NottyTerminalError::MissingProfileAccount);
+            };
+            write_token_profile(
+                token_profile,
+                bump,
+                self.creator_mint.key(),
+                self.creator.key(),
+                profile,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn launch(&mut self, args: &CreateTokenArgs, bumps: &TokenLaunchBumps) -> Result<()> {
+        require!(
+            args.start_mcap == 50_000_000_000, // Must be 50 SOL
+            NottyTerminalError::InvalidStartingMcap
+        );
+
+        require!(
+            args.target_sol == 450_000_000_000, // Must be 450 SOL
+            NottyTerminalError::InvalidTargetMcap
+        );
+
+        let venue = args.venue.unwrap_or_default();
+        require!(
+            self.global_state.is_venue_allowed(venue),
+            NottyTerminalError::VenueNotAllowed
+        );
+        require!(
+            venue.supports_token_program(&self.token_program.key()),
+            NottyTerminalError::UnsupportedTokenProgram
+        );
+
+        let decimals = args.mint_decimals();
+        require!(
+            decimals <= MAX_TOKEN_DECIMALS,
+            NottyTerminalError::InvalidDecimals
+        );
+
+        require!(
+            self.global_state.is_total_supply_allowed(args.total_supply),
+            NottyTerminalError::InvalidTotalSupply
+        );
+
+        // lamports per whole token, a supply too large for the start mcap would round it to 0
+        let initial_price_per_token = args
+            .start_mcap
+            .checked_div(args.total_supply)
+            .ok_or(NottyTerminalError::InvalidTotalSupply)?;
+        require!(
+            initial_price_per_token > 0,
+            NottyTerminalError::InvalidTotalSupply
+        );
+
+        let total_supply_base_units = 10u64
+            .checked_pow(decimals as u32)
+            .and_then(|unit| args.total_supply.checked_mul(unit))
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        // the curve prices per million base units
+        require!(
+            total_supply_base_units >= 1_000_000,
+            NottyTerminalError::InvalidTotalSupply
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        // a start in the past simply opens trading right away
+        let trading_starts_at = args.trading_starts_at.unwrap_or(now).max(now);
+        require!(
+            trading_starts_at - now <= MAX_TRADING_START_DELAY,
+            NottyTerminalError::InvalidTradingStart
+        );
+
+        let (allowlist_root, allowlist_ends_at) = match args.allowlist {
+            Some(allowlist) => {
+                require!(
+                    allowlist.ends_at > trading_starts_at
+                        && allowlist.ends_at - trading_starts_at <= MAX_ALLOWLIST_DURATION,
+                    NottyTerminalError::InvalidAllowlist
+                );
+                (allowlist.merkle_root, allowlist.ends_at)
+            }
+            None => ([0; 32], 0),
+        };
+
+        let graduation_deadline = match args.graduation_deadline {
+            Some(deadline) => {
+                require!(
+                    deadline > trading_starts_at.max(allowlist_ends_at)
+                        && deadline - trading_starts_at <= MAX_GRADUATION_DEADLINE,
+                    NottyTerminalError::InvalidGraduationDeadline
+                );
+                deadline
+            }
+            None => 0,
+        };
+
+        let creator_verified = self
+            .creator_verification
+            .as_ref()
+            .is_some_and(|verification| verification.is_active_creator(now));
+
+        // pay token creation fee
+        let cpi_transfer_accounts = Transfer {
+            from: self.creator.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+
+        let (platform_fee, seed_liquidity) = self
+            .global_state
+            .split_listing_fee(self.global_state.listing_fee(creator_verified))?;
+
+        transfer(
+            CpiContext::new(self.system_program.to_account_info(), cpi_transfer_accounts),
+            platform_fee,
+        )?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"token_state",
+            &self.creator_mint.key().to_bytes(),
+            &[bumps.token_state],
+        ]];
+
+        // transfer rent exempt amount
+        let rent_exempt: u64 =
+            Rent::get()?.minimum_balance(self.sol_vault.to_account_info().data_len());
+
+        let cpi_rent_exempt_accounts = Transfer {
+            from: self.creator.to_account_info(),
+            to: self.sol_vault.to_account_info(),
+        };
+
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                cpi_rent_exempt_accounts,
+            ),
+            rent_exempt,
+        )?;
+
+        // rest of the fee seeds the curve liquidity
+        let cpi_accounts_transfer_to_sol_vault = Transfer {
+            from: self.creator.to_account_info(),
+            to: self.sol_vault.to_account_info(),
+        };
+
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                cpi_accounts_transfer_to_sol_vault,
+            ),
+            seed_liquidity,
+        )?;
+
+        self.create_ata(&self.token_vault, self.token_state.to_account_info())?;
+        self.create_ata(&self.creator_ata, self.creator.to_account_info())?;
+
+        let mint_to_accounts = MintTo {
+            authority: self.token_state.to_account_info(),
+            mint: self.creator_mint.to_account_info(),
+            to: self.token_vault.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            mint_to_accounts,
+            signer_seeds,
+        );
+
+        mint_to(cpi_context, total_supply_base_units)?;
+
+        // supply is fixed from here on, nobody can mint again
+        self.revoke_authority(AuthorityType::MintTokens, signer_seeds)?;
+
+        let freeze_authority_revoked = self.global_state.revoke_freeze_at_creation;
+        if freeze_authority_revoked {
+            self.revoke_authority(AuthorityType::FreezeAccount, signer_seeds)?;
+        }
+
+        self.creator_profile
+            .init_if_empty(bumps.creator_profile, self.creator.key());
+        if !self.global_state.is_rate_limit_exempt(&self.creator.key()) {
+            self.creator_profile
+                .check_rate_limit(&self.global_state.launch_rate_limit, now)?;
+        }
+        self.creator_profile
+            .record_launch(self.creator_mint.key(), now)?;
+
+        // set Token state
+        self.token_state.set_inner(TokenState {
+            bump: bumps.token_state,
+            migrated: false,
+            mint: self.creator_mint.key(),
+            initial_price_per_token,
+            sol_raised: 0,
+            seed_liquidity,
+            trading_starts_at,
+            allowlist_root,
+            allowlist_ends_at,
+            graduation_deadline,
+            refund_pool: RefundPool::default(),
+            tokens_sold: 0,
+            total_supply: args.total_supply,
+            sol_vault_bump: bumps.sol_vault,
+            start_mcap: args.start_mcap,
+            target_sol: args.target_sol,
+            pool: None,
+            migration_timestamp: 0,
+            creator: self.creator.key(),
+            venue,
+            finalized: false,
+            migration_stage: MigrationStage::Trading,
+            migration_amounts: MigrationAmounts::default(),
+            dev_buy_amount: 0,
+            dev_buy_locked_amount: 0,
+            dev_buy_cliff_ts: 0,
+            dev_buy_unlock_end_ts: 0,
+            decimals,
+            version: TOKEN_STATE_VERSION,
+        });
+
+        emit!(TokenCreated {
+            migrated: false,
+            mint: self.creator_mint.key(),
+            initial_price_per_token,
+            sol_raised: 0,
+            seed_liquidity,
+            tokens_sold: 0,
+            total_supply: args.total_supply,
+            start_mcap: args.start_mcap,
+            target_sol: args.target_sol,
+            pool: None,
+            migration_timestamp: 0,
+            creator: self.creator.key(),
+            uri: args.token_uri.clone(),
+            venue,
+            mint_authority: None,
+            freeze_authority: (!freeze_authority_revoked).then(|| self.token_state.key()),
+            creator_verified,
+            trading_starts_at,
+            allowlist_ends_at,
+            graduation_deadline,
+        });
+
+        if let Some(amount) = args.initial_buy_amount {
+            self.dev_buy(
+                amount,
+                args.max_sol_cost,
+                args.dev_buy_vesting,
+                bumps,
+                signer_seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates the `authority` ATA of the launch mint at `account` unless it already exists
+    fn create_ata(
+        &self,
+        account: &UncheckedAccount<'info>,
+        authority: AccountInfo<'info>,
+    ) -> Result<()> {
+        create_idempotent(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            Create {
+                payer: self.creator.to_account_info(),
+                associated_token: account.to_account_info(),
+                authority,
+                mint: self.creator_mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))
+    }
+
+    fn revoke_authority(
+        &self,
+        authority_type: AuthorityType,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        set_authority(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: self.token_state.to_account_info(),
+                    account_or_mint: self.creator_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            authority_type,
+            None,
+        )
+    }
+
+    /// Creator buy in the launch transaction, priced and charged exactly like `purchase_token`.
+    /// With a vesting schedule the tokens land in the vesting vault instead of the creator ATA
+    fn dev_buy(
+        &mut self,
+        amount: u64,
+        max_sol_cost: u64,
+        vesting: Option<VestingSchedule>,
+        bumps: &TokenLaunchBumps,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require!(amount > 0, NottyTerminalError::InvalidAmount);
+
+        let destination = match vesting {
+            Some(schedule) => self.lock_dev_buy(amount, schedule, bumps.creator_vesting)?,
+            None => self.creator_ata.to_account_info(),
+        };
+
+        let quote = PurchaseQuote::new(&self.token_state, &self.global_state, amount)?;
+        require!(
+            quote.total_cost <= max_sol_cost,
+            NottyTerminalError::SlippageExceeded
+        );
+
+        let token_vault = self.token_vault.key();
+        let sol_vault_seeds: &[&[&[u8]]] = &[&[
+            b"sol_vault",
+            token_vault.as_ref(),
+            &[self.token_state.sol_vault_bump],
+        ]];
+
+        pay_for_purchase(
+            &quote,
+            self.creator.to_account_info(),
+            self.sol_vault.to_account_info(),
+            self.vault.to_account_info(),
+            self.system_program.to_account_info(),
+            sol_vault_seeds,
+        )?;
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    authority: self.token_state.to_account_info(),
+                    from: self.token_vault.to_account_info(),
+                    mint: self.creator_mint.to_account_info(),
+                    to: destination,
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.token_state.decimals,
+        )?;
+
+        self.token_state.dev_buy_amount = amount;
+        record_purchase(
+            &mut self.token_state,
+            &mut self.global_state,
+            &quote,
+            amount,
+            self.creator.key(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Sets up the vesting account for the dev-buy and returns the vault to send it to
+    fn lock_dev_buy(
+        &mut self,
+        amount: u64,
+        schedule: VestingSchedule,
+        bump: Option<u8>,
+    ) -> Result<AccountInfo<'info>> {
+        require!(
+            schedule.duration_seconds > 0
+                && schedule.cliff_seconds >= 0
+                && schedule.cliff_seconds <= schedule.duration_seconds,
+            NottyTerminalError::InvalidVestingSchedule
+        );
+
+        let (Some(creator_vesting), Some(vesting_vault), Some(bump)) = (
+            self.creator_vesting.as_mut(),
+            self.vesting_vault.as_ref(),
+            bump,
+        ) else {
+            return err!(NottyTerminalError::MissingVestingAccounts);
+        };
+
+        create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            Create {
+                payer: self.creator.to_account_info(),
+                associated_token: vesting_vault.to_account_info(),
+                authority: creator_vesting.to_account_info(),
+                mint: self.creator_mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))?;
+
+        let start_ts = Clock::get()?.unix_timestamp;
+        let cliff_ts = start_ts
+            .checked_add(schedule.cliff_seconds)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        let end_ts = start_ts
+            .checked_add(schedule.duration_seconds)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+
+        creator_vesting.set_inner(CreatorVesting {
+            bump,
+            mint: self.creator_mint.key(),
+            creator: self.creator.key(),
+            total_amount: amount,
+            claimed_amount: 0,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        });
+
+        self.token_state.dev_buy_locked_amount = amount;
+        self.token_state.dev_buy_cliff_ts = cliff_ts;
+        self.token_state.dev_buy_unlock_end_ts = end_ts;
+
+        emit!(DevBuyLocked {
+            mint: self.creator_mint.key(),
+            creator: self.creator.key(),
+            amount,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        });
+
+        Ok(vesting_vault.to_account_info())
+    }
+}
@@ -0,0 +1,244 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    metadata::{
+        create_metadata_accounts_v3,
+        mpl_token_metadata::{self, types::DataV2},
+        update_metadata_accounts_v2, CreateMetadataAccountsV3, MetadataAccount,
+        UpdateMetadataAccountsV2,
+    },
+    token_2022::spl_token_2022::{
+        extension::{BaseStateWithExtensions, StateWithExtensions},
+        state::Mint as MintState,
+    },
+    token_2022_extensions::{
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::{Field, TokenMetadata},
+        token_metadata_update_authority, token_metadata_update_field, TokenMetadataUpdateAuthority,
+        TokenMetadataUpdateField,
+    },
+};
+
+use super::create_token::CreateTokenArgs;
+use crate::error::NottyTerminalError;
+
+/// Metadata of a launch token, either in its Metaplex account or in the Token-2022 mint
+/// itself. `token_state` is the update authority in both cases.
+pub struct LaunchMetadata<'a, 'info> {
+    pub mint: AccountInfo<'info>,
+    pub token_state: AccountInfo<'info>,
+    pub launch_token_program: AccountInfo<'info>,
+    /// Metaplex PDA of the mint, left empty by Token-2022 launches
+    pub metadata_account: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+/// Name, symbol and uri currently stored for the token
+pub struct CurrentMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+impl<'a, 'info> LaunchMetadata<'a, 'info> {
+    fn token_2022_metadata(&self) -> Option<TokenMetadata> {
+        let data = self.mint.try_borrow_data().ok()?;
+        let mint = StateWithExtensions::<MintState>::unpack(&data).ok()?;
+        mint.get_variable_len_extension::<TokenMetadata>().ok()
+    }
+
+    fn metaplex_metadata(&self) -> Result<MetadataAccount> {
+        require_keys_eq!(
+            *self.metadata_account.owner,
+            mpl_token_metadata::ID,
+            NottyTerminalError::MissingTokenMetadata
+        );
+        let data = self.metadata_account.try_borrow_data()?;
+        MetadataAccount::try_deserialize(&mut &data[..])
+    }
+
+    /// Creates the Metaplex account of a spl token launch, signed by token_state as
+    /// mint authority so it must run before the launch renounces it
+    pub fn create_metaplex(
+        &self,
+        args: &CreateTokenArgs,
+        payer: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        rent: AccountInfo<'info>,
+    ) -> Result<()> {
+        let datav2 = DataV2 {
+            name: args.name.clone(),
+            symbol: args.token_symbol.clone(),
+            uri: args.token_uri.clone(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.clone(),
+                CreateMetadataAccountsV3 {
+                    metadata: self.metadata_account.clone(),
+                    mint: self.mint.clone(),
+                    mint_authority: self.token_state.clone(),
+                    payer,
+                    rent,
+                    system_program,
+                    update_authority: self.token_state.clone(),
+                },
+                self.signer_seeds,
+            ),
+            datav2,
+            true, // mutable until the migration locks it
+            false,
+            None,
+        )
+    }
+
+    pub fn current(&self) -> Result<CurrentMetadata> {
+        if let Some(metadata) = self.token_2022_metadata() {
+            return Ok(CurrentMetadata {
+                name: metadata.name,
+                symbol: metadata.symbol,
+                uri: metadata.uri,
+            });
+        }
+
+        // Metaplex pads the stored strings with zeros
+        let metadata = self.metaplex_metadata()?;
+        Ok(CurrentMetadata {
+            name: metadata.name.trim_end_matches('\0').to_string(),
+            symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+            uri: metadata.uri.trim_end_matches('\0').to_string(),
+        })
+    }
+
+    /// Rewrites the name and uri, `payer` covers the extra rent of a longer Token-2022 entry
+    pub fn update(
+        &self,
+        name: &str,
+        uri: &str,
+        payer: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+    ) -> Result<()> {
+        let Some(mut metadata) = self.token_2022_metadata() else {
+            return self.update_metaplex(name, uri);
+        };
+
+        let current_len = metadata
+            .tlv_size_of()
+            .map_err(|_| NottyTerminalError::NumericalOverflow)?;
+        metadata.name = name.to_string();
+        metadata.uri = uri.to_string();
+        let new_len = metadata
+            .tlv_size_of()
+            .map_err(|_| NottyTerminalError::NumericalOverflow)?;
+
+        // Token-2022 reallocates the mint but doesn't fund it
+        let required_lamports = Rent::get()?
+            .minimum_balance(self.mint.data_len() + new_len.saturating_sub(current_len))
+            .saturating_sub(self.mint.lamports());
+        if required_lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    system_program,
+                    Transfer {
+                        from: payer,
+                        to: self.mint.clone(),
+                    },
+                ),
+                required_lamports,
+            )?;
+        }
+
+        for (field, value) in [(Field::Name, name), (Field::Uri, uri)] {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    self.launch_token_program.clone(),
+                    TokenMetadataUpdateField {
+                        program_id: self.launch_token_program.clone(),
+                        metadata: self.mint.clone(),
+                        update_authority: self.token_state.clone(),
+                    },
+                    self.signer_seeds,
+                ),
+                field,
+                value.to_string(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn update_metaplex(&self, name: &str, uri: &str) -> Result<()> {
+        let metadata = self.metaplex_metadata()?;
+
+        let data = DataV2 {
+            name: name.to_string(),
+            symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+            uri: uri.to_string(),
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            creators: metadata.creators.clone(),
+            collection: metadata.collection.clone(),
+            uses: metadata.uses.clone(),
+        };
+
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.clone(),
+                UpdateMetadataAccountsV2 {
+                    metadata: self.metadata_account.clone(),
+                    update_authority: self.token_state.clone(),
+                },
+                self.signer_seeds,
+            ),
+            None,
+            Some(data),
+            None,
+            None,
+        )
+    }
+
+    /// Freezes the metadata for good, nobody can update it afterwards
+    pub fn lock(&self) -> Result<()> {
+        if self.token_2022_metadata().is_some() {
+            return token_metadata_update_authority(
+                CpiContext::new_with_signer(
+                    self.launch_token_program.clone(),
+                    TokenMetadataUpdateAuthority {
+                        program_id: self.launch_token_program.clone(),
+                        metadata: self.mint.clone(),
+                        current_authority: self.token_state.clone(),
+                        new_authority: self.token_state.clone(),
+                    },
+                    self.signer_seeds,
+                ),
+                OptionalNonZeroPubkey::default(),
+            );
+        }
+
+        if !self.metaplex_metadata()?.is_mutable {
+            return Ok(());
+        }
+
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.clone(),
+                UpdateMetadataAccountsV2 {
+                    metadata: self.metadata_account.clone(),
+                    update_authority: self.token_state.clone(),
+                },
+                self.signer_seeds,
+            ),
+            None,
+            None,
+            None,
+            Some(false),
+        )
+    }
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::NottyTerminalError, GlobalState, LaunchRateLimit, MetadataPolicy, MigrationVenue,
+    MAX_AMM_CONFIGS, MAX_RATE_LIMIT_EXEMPTIONS, MAX_UNWIND_DELAY,
+};
+
+#[derive(Accounts)]
+pub struct UpdateGlobalState<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = admin @NottyTerminalError::UnauthorizedAdmin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+impl<'info> UpdateGlobalState<'info> {
+    pub fn handle_update_global_state(&mut self, args: UpdateGlobalStateArgs) -> Result<()> {
+        if let Some(venues) = args.allowed_venues {
+            self.global_state.allowed_venues =
+                venues.iter().fold(0, |mask, venue| mask | venue.mask());
+        }
+
+        if let Some(amm_configs) = args.allowed_amm_configs {
+            require!(
+                !amm_configs.is_empty() && amm_configs.len() <= MAX_AMM_CONFIGS,
+                NottyTerminalError::InvalidAmmConfig
+            );
+            self.global_state.allowed_amm_configs = amm_configs;
+        }
+
+        if let Some(rent_receiver) = args.rent_receiver {
+            self.global_state.rent_receiver = rent_receiver;
+        }
+
+        if let Some(revoke_freeze_at_creation) = args.revoke_freeze_at_creation {
+            self.global_state.revoke_freeze_at_creation = revoke_freeze_at_creation;
+        }
+
+        if let Some(metadata_policy) = args.metadata_policy {
+            metadata_policy.validate_limits()?;
+            self.global_state.metadata_policy = metadata_policy;
+        }
+
+        if let Some(verified_listing_fee_lamport) = args.verified_listing_fee_lamport {
+            require!(
+                verified_listing_fee_lamport <= self.global_state.listing_fee_lamport,
+                NottyTerminalError::InvalidListingFee
+            );
+            self.global_state.verified_listing_fee_lamport = verified_listing_fee_lamport;
+        }
+
+        if let Some(listing_fee_platform_bps) = args.listing_fee_platform_bps {
+            require!(
+                listing_fee_platform_bps <= 10_000,
+                NottyTerminalError::InvalidFeeSplit
+            );
+            self.global_state.listing_fee_platform_bps = listing_fee_platform_bps;
+        }
+
+        if let Some(launch_rate_limit) = args.launch_rate_limit {
+            launch_rate_limit.validate()?;
+            self.global_state.launch_rate_limit = launch_rate_limit;
+        }
+
+        if let Some(rate_limit_exempt) = args.rate_limit_exempt {
+            require!(
+                rate_limit_exempt.len() <= MAX_RATE_LIMIT_EXEMPTIONS,
+                NottyTerminalError::InvalidRateLimit
+            );
+            self.global_state.rate_limit_exempt = rate_limit_exempt;
+        }
+
+        if let Some(unwind_delay) = args.unwind_delay {
+            require!(
+                (0..=MAX_UNWIND_DELAY).contains(&unwind_delay),
+                NottyTerminalError::InvalidUnwindDelay
+            );
+            self.global_state.unwind_delay = unwind_delay;
+        }
+
+        if let Some(min_total_supply) = args.min_total_supply {
+            self.global_state.min_total_supply = min_total_supply;
+        }
+
+        if let Some(max_total_supply) = args.max_total_supply {
+            self.global_state.max_total_supply = max_total_supply;
+        }
+
+        require!(
+            self.global_state.min_total_supply > 0
+                && self.global_state.min_total_supply <= self.global_state.max_total_supply,
+            NottyTerminalError::InvalidSupplyBounds
+        );
+
+        Ok(())
+    }
+}
+
+/// Every field is optional, only the ones provided are updated
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct UpdateGlobalStateArgs {
+    pub allowed_venues: Option<Vec<MigrationVenue>>, // full whitelist, replaces the current one
+    pub allowed_amm_configs: Option<Vec<Pubkey>>,    // full whitelist, replaces the current one
+    pub rent_receiver: Option<Pubkey>,
+    pub revoke_freeze_at_creation: Option<bool>,
+    pub metadata_policy: Option<MetadataPolicy>,
+    pub min_total_supply: Option<u64>,             // whole tokens
+    pub max_total_supply: Option<u64>,             // whole tokens
+    pub verified_listing_fee_lamport: Option<u64>, // at most listing_fee_lamport
+    pub listing_fee_platform_bps: Option<u16>,     // 10000 sends the whole fee to the platform
+    pub launch_rate_limit: Option<LaunchRateLimit>,
+    pub rate_limit_exempt: Option<Vec<Pubkey>>, // full list, replaces the current one
+    pub unwind_delay: Option<i64>,              // seconds, at most MAX_UNWIND_DELAY
+}
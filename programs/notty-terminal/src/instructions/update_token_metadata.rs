@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::Metadata,
+    token_interface::{Mint, TokenInterface},
+};
+
+use super::token_metadata::LaunchMetadata;
+use crate::{error::NottyTerminalError, GlobalState, TokenState};
+
+/// Lets the creator fix the name or uri of a token while its curve is still trading.
+/// The symbol stays the one registered at launch.
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    /// Pays the extra rent when a Token-2022 entry grows
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == token_state.mint.key() @NottyTerminalError::WrongMint,
+        mint::token_program = launch_token_program,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            b"token_state", token_mint.key().as_ref()
+        ],
+        bump = token_state.bump,
+        has_one = creator @NottyTerminalError::UnauthorizedCreator,
+    )]
+    pub token_state: Account<'info, TokenState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    /// CHECK: Metaplex metadata of the mint, empty for Token-2022 launches
+    pub metadata_account: UncheckedAccount<'info>,
+
+    pub launch_token_program: Interface<'info, TokenInterface>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateTokenMetadata<'info> {
+    pub fn handle_update_token_metadata(&mut self, args: UpdateTokenMetadataArgs) -> Result<()> {
+        // metadata is locked once the migration starts
+        require!(
+            self.token_state.is_trading_open(),
+            NottyTerminalError::MetadataLocked
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"token_state",
+            self.token_state.mint.as_ref(),
+            &[self.token_state.bump],
+        ]];
+        let metadata = LaunchMetadata {
+            mint: self.token_mint.to_account_info(),
+            token_state: self.token_state.to_account_info(),
+            launch_token_program: self.launch_token_program.to_account_info(),
+            metadata_account: self.metadata_account.to_account_info(),
+            token_metadata_program: self.token_metadata_program.to_account_info(),
+            signer_seeds,
+        };
+
+        let current = metadata.current()?;
+        let name = args.name.unwrap_or(current.name);
+        let uri = args.uri.unwrap_or(current.uri);
+        self.global_state
+            .metadata_policy
+            .validate(&name, &current.symbol, &uri)?;
+
+        metadata.update(
+            &name,
+            &uri,
+            self.creator.to_account_info(),
+            self.system_program.to_account_info(),
+        )?;
+
+        emit!(TokenMetadataUpdated {
+            mint: self.token_mint.key(),
+            creator: self.creator.key(),
+            name,
+            symbol: current.symbol,
+            uri,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+/// Fields left to `None` keep their current value
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct UpdateTokenMetadataArgs {
+    pub name: Option<String>,
+    pub uri: Option<String>,
+}
+
+#[event]
+pub struct TokenMetadataUpdated {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub timestamp: i64,
+}
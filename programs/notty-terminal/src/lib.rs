@@ -13,8 +13,6 @@ declare_id!("ntt7FdxskUiwbG52JpdCeubLvtFcZe3SJm6cT18PouG");
 
 #[program]
 pub mod notty_terminal {
-    use crate::error::NottyTerminalError;
-
     use super::*;
 
     pub fn initialize(ctx: Context<InitializeGlobalState>, args: InitializeArgs) -> Result<()> {
@@ -27,8 +25,19 @@ pub mod notty_terminal {
         Ok(())
     }
 
+    pub fn create_token_2022(ctx: Context<CreateToken2022>, args: CreateTokenArgs) -> Result<()> {
+        ctx.accounts.handle_create_token_2022(args, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn create_token_pda(ctx: Context<CreateTokenPda>, args: CreateTokenArgs) -> Result<()> {
+        ctx.accounts.handle_create_token_pda(args, &ctx.bumps)?;
+        Ok(())
+    }
+
     pub fn purchase_token(ctx: Context<TokenInteraction>, args: PurchaseTokenArgs) -> Result<()> {
-        ctx.accounts.handle_purchase(args)?;
+        ctx.accounts
+            .handle_purchase(args, ctx.bumps.allowlist_purchase)?;
         Ok(())
     }
 
@@ -37,34 +46,119 @@ pub mod notty_terminal {
         Ok(())
     }
 
-    pub fn migrate_to_raydium(ctx: Context<Launch>, params: LaunchParam) -> Result<()> {
-        let init_amount_0 = ctx
-            .accounts
-            .token_state
-            .total_supply
-            .checked_mul(1_000_000_000)
-            .and_then(|res| res.checked_sub(ctx.accounts.token_state.tokens_sold))
-            .ok_or(NottyTerminalError::InsufficientVaultBalance)?;
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        ctx.accounts.handle_claim_vested()?;
+        Ok(())
+    }
+
+    pub fn update_global_state(
+        ctx: Context<UpdateGlobalState>,
+        args: UpdateGlobalStateArgs,
+    ) -> Result<()> {
+        ctx.accounts.handle_update_global_state(args)?;
+        Ok(())
+    }
+
+    pub fn reserve_symbol(ctx: Context<ReserveSymbol>, args: ReserveSymbolArgs) -> Result<()> {
+        ctx.accounts.handle_reserve_symbol(args, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        args: UpdateTokenMetadataArgs,
+    ) -> Result<()> {
+        ctx.accounts.handle_update_token_metadata(args)?;
+        Ok(())
+    }
+
+    pub fn set_verification(
+        ctx: Context<SetVerification>,
+        args: SetVerificationArgs,
+    ) -> Result<()> {
+        ctx.accounts.handle_set_verification(args, &ctx.bumps)?;
+        Ok(())
+    }
 
-        let init_amount_1 = ctx.accounts.token_state.sol_raised;
-        let open_time = match params.time {
-            Some(value) => value as u64,
-            None => Clock::get()?.unix_timestamp as u64,
-        };
+    pub fn set_token_profile(
+        ctx: Context<SetTokenProfile>,
+        args: TokenProfileArgs,
+    ) -> Result<()> {
+        ctx.accounts.handle_set_token_profile(args, &ctx.bumps)?;
+        Ok(())
+    }
 
-        let new_init_0_amount: u64;
-        let new_init_1_amount: u64;
+    pub fn prepare_migration(ctx: Context<PrepareMigration>) -> Result<()> {
+        ctx.accounts.handle_prepare_migration()?;
+        Ok(())
+    }
 
-        if params.token_mint.key() == ctx.accounts.token_0_mint.key() {
-            new_init_0_amount = init_amount_0;
-            new_init_1_amount = init_amount_1;
-        } else {
-            new_init_0_amount = init_amount_1;
-            new_init_1_amount = init_amount_0;
-        }
+    pub fn check_migration(ctx: Context<CheckMigration>) -> Result<MigrationReport> {
+        ctx.accounts.handle_check_migration()
+    }
 
-        ctx.accounts
-            .handle_launch(new_init_0_amount, new_init_1_amount, open_time)?;
+    pub fn execute_migration(ctx: Context<Launch>, params: LaunchParam) -> Result<()> {
+        ctx.accounts.execute_migration(params, &ctx.bumps.common)?;
+        Ok(())
+    }
+
+    pub fn execute_migration_raydium_amm(
+        ctx: Context<LaunchRaydiumAmm>,
+        params: LaunchRaydiumAmmParam,
+    ) -> Result<()> {
+        ctx.accounts.execute_migration(params, &ctx.bumps.common)?;
+        Ok(())
+    }
+
+    pub fn execute_migration_orca_whirlpool(
+        ctx: Context<LaunchOrcaWhirlpool>,
+        params: LaunchOrcaWhirlpoolParam,
+    ) -> Result<()> {
+        ctx.accounts.execute_migration(params, &ctx.bumps.common)?;
+        Ok(())
+    }
+
+    pub fn execute_migration_meteora_damm(
+        ctx: Context<LaunchMeteoraDamm>,
+        params: LaunchMeteoraDammParam,
+    ) -> Result<()> {
+        ctx.accounts.execute_migration(params, &ctx.bumps.common)?;
+        Ok(())
+    }
+
+    pub fn finalize_migration(ctx: Context<FinalizeMigration>) -> Result<()> {
+        ctx.accounts.handle_finalize_migration()?;
+        Ok(())
+    }
+
+    pub fn open_refunds(ctx: Context<OpenRefunds>) -> Result<()> {
+        ctx.accounts.handle_open_refunds()?;
+        Ok(())
+    }
+
+    pub fn redeem_refund(ctx: Context<RedeemRefund>, args: RedeemRefundArgs) -> Result<()> {
+        ctx.accounts.handle_redeem_refund(args)?;
+        Ok(())
+    }
+
+    pub fn emergency_unwind(
+        ctx: Context<EmergencyUnwind>,
+        args: EmergencyUnwindArgs,
+    ) -> Result<()> {
+        ctx.accounts.handle_emergency_unwind(args)?;
+        Ok(())
+    }
+
+    pub fn migrate_global_state(
+        ctx: Context<MigrateGlobalState>,
+        args: MigrateGlobalStateArgs,
+    ) -> Result<()> {
+        ctx.accounts.handle_migrate_global_state(args)?;
+        Ok(())
+    }
+
+    pub fn migrate_token_state(ctx: Context<MigrateTokenState>) -> Result<()> {
+        ctx.accounts.handle_migrate_token_state()?;
         Ok(())
     }
 }
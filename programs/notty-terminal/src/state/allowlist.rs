@@ -0,0 +1,148 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::error::NottyTerminalError;
+
+/// Presale phase of a curve, only buyers in the Merkle tree can purchase until `ends_at`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Debug)]
+pub struct AllowlistConfig {
+    pub merkle_root: [u8; 32],
+    pub ends_at: i64,
+}
+
+/// Buyer side of an allowlist purchase, `allocation` is the one committed in the leaf
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AllowlistProof {
+    pub allocation: u64, // max base units the buyer can purchase in the phase, 0 = no cap
+    pub proof: Vec<[u8; 32]>,
+}
+
+impl AllowlistProof {
+    /// Leaves are sha256(user || allocation le bytes), pairs are hashed sorted
+    pub fn verify(&self, merkle_root: &[u8; 32], user: &Pubkey) -> Result<()> {
+        let leaf = hashv(&[user.as_ref(), &self.allocation.to_le_bytes()]).to_bytes();
+
+        let computed_root = self.proof.iter().fold(leaf, |node, sibling| {
+            if node <= *sibling {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            }
+        });
+
+        require!(
+            computed_root == *merkle_root,
+            NottyTerminalError::NotAllowlisted
+        );
+        Ok(())
+    }
+}
+
+/// Base units a buyer purchased during the allowlist phase of a token
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistPurchase {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub purchased: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(user: &Pubkey, allocation: u64) -> [u8; 32] {
+        hashv(&[user.as_ref(), &allocation.to_le_bytes()]).to_bytes()
+    }
+
+    fn parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[&a, &b]).to_bytes()
+        } else {
+            hashv(&[&b, &a]).to_bytes()
+        }
+    }
+
+    /// Four buyers, returns them with their allocations, the root and the proof of the third
+    fn four_leaf_tree() -> ([(Pubkey, u64); 4], [u8; 32], Vec<[u8; 32]>) {
+        let buyers = [
+            (Pubkey::new_unique(), 1_000),
+            (Pubkey::new_unique(), 0),
+            (Pubkey::new_unique(), 5_000),
+            (Pubkey::new_unique(), 2_500),
+        ];
+        let leaves = buyers.map(|(user, allocation)| leaf(&user, allocation));
+        let left = parent(leaves[0], leaves[1]);
+        let right = parent(leaves[2], leaves[3]);
+
+        (buyers, parent(left, right), vec![leaves[3], left])
+    }
+
+    #[test]
+    fn valid_proof_is_accepted() {
+        let (buyers, root, proof) = four_leaf_tree();
+        let (user, allocation) = buyers[2];
+
+        AllowlistProof { allocation, proof }
+            .verify(&root, &user)
+            .unwrap();
+    }
+
+    #[test]
+    fn proof_with_another_allocation_is_rejected() {
+        let (buyers, root, proof) = four_leaf_tree();
+        let (user, allocation) = buyers[2];
+
+        let err = AllowlistProof {
+            allocation: allocation + 1,
+            proof,
+        }
+        .verify(&root, &user)
+        .unwrap_err();
+        assert_eq!(err, NottyTerminalError::NotAllowlisted.into());
+    }
+
+    #[test]
+    fn proof_of_another_user_is_rejected() {
+        let (buyers, root, proof) = four_leaf_tree();
+        let (_, allocation) = buyers[2];
+
+        let err = AllowlistProof { allocation, proof }
+            .verify(&root, &buyers[3].0)
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::NotAllowlisted.into());
+    }
+
+    #[test]
+    fn tampered_sibling_is_rejected() {
+        let (buyers, root, mut proof) = four_leaf_tree();
+        let (user, allocation) = buyers[2];
+        proof[1][0] ^= 1;
+
+        let err = AllowlistProof { allocation, proof }
+            .verify(&root, &user)
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::NotAllowlisted.into());
+    }
+
+    #[test]
+    fn single_leaf_root_takes_an_empty_proof() {
+        let user = Pubkey::new_unique();
+        let root = leaf(&user, 750);
+
+        AllowlistProof {
+            allocation: 750,
+            proof: vec![],
+        }
+        .verify(&root, &user)
+        .unwrap();
+
+        let err = AllowlistProof {
+            allocation: 750,
+            proof: vec![],
+        }
+        .verify(&root, &Pubkey::new_unique())
+        .unwrap_err();
+        assert_eq!(err, NottyTerminalError::NotAllowlisted.into());
+    }
+}
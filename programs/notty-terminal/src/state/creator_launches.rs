@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+/// Per creator launch counter, the nonce of the next PDA-derived launch mint
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorLaunches {
+    pub bump: u8,
+    pub creator: Pubkey,
+    pub launch_count: u64, // PDA mints created so far, seeds the next one
+}
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::NottyTerminalError, LaunchRateLimit, MAX_LAUNCHES_PER_WINDOW, MAX_RECENT_MINTS,
+};
+
+/// Track record of a creator, kept up to date by the launch and migration instructions
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorProfile {
+    pub bump: u8,
+    pub creator: Pubkey,
+    pub launch_count: u64,
+    pub graduation_count: u64,
+    pub total_sol_raised: u64, // lamports raised by the creator's graduated curves
+    #[max_len(MAX_RECENT_MINTS)]
+    pub recent_mints: Vec<Pubkey>, // latest launches, newest last
+    pub last_launch_ts: i64,
+    pub last_graduation_ts: i64,
+    #[max_len(MAX_LAUNCHES_PER_WINDOW)]
+    pub recent_launch_ts: Vec<i64>, // latest launch times, for the rolling rate limit
+}
+
+impl CreatorProfile {
+    /// Sets the identity of a profile created by `init_if_needed`
+    pub fn init_if_empty(&mut self, bump: u8, creator: Pubkey) {
+        if self.creator == Pubkey::default() {
+            self.bump = bump;
+            self.creator = creator;
+        }
+    }
+
+    /// Fails when the creator already launched `max_launches` tokens in the last
+    /// `window_seconds`
+    pub fn check_rate_limit(&self, rate_limit: &LaunchRateLimit, now: i64) -> Result<()> {
+        if !rate_limit.is_enabled() {
+            return Ok(());
+        }
+
+        let window_start = now.saturating_sub(rate_limit.window_seconds);
+        let launches_in_window = self
+            .recent_launch_ts
+            .iter()
+            .filter(|ts| **ts > window_start)
+            .count();
+        require!(
+            launches_in_window < rate_limit.max_launches as usize,
+            NottyTerminalError::LaunchRateLimited
+        );
+
+        Ok(())
+    }
+
+    pub fn record_launch(&mut self, mint: Pubkey, now: i64) -> Result<()> {
+        self.launch_count = self
+            .launch_count
+            .checked_add(1)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+
+        if self.recent_mints.len() == MAX_RECENT_MINTS {
+            self.recent_mints.remove(0);
+        }
+        self.recent_mints.push(mint);
+        self.last_launch_ts = now;
+
+        if self.recent_launch_ts.len() == MAX_LAUNCHES_PER_WINDOW {
+            self.recent_launch_ts.remove(0);
+        }
+        self.recent_launch_ts.push(now);
+
+        Ok(())
+    }
+
+    pub fn record_graduation(&mut self, sol_raised: u64, now: i64) -> Result<()> {
+        self.graduation_count = self
+            .graduation_count
+            .checked_add(1)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        self.total_sol_raised = self
+            .total_sol_raised
+            .checked_add(sol_raised)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        self.last_graduation_ts = now;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn new_profile() -> CreatorProfile {
+        CreatorProfile {
+            bump: 0,
+            creator: Pubkey::new_unique(),
+            launch_count: 0,
+            graduation_count: 0,
+            total_sol_raised: 0,
+            recent_mints: vec![],
+            last_launch_ts: 0,
+            last_graduation_ts: 0,
+            recent_launch_ts: vec![],
+        }
+    }
+
+    fn rate_limit(max_launches: u8) -> LaunchRateLimit {
+        LaunchRateLimit {
+            max_launches,
+            window_seconds: 100,
+        }
+    }
+
+    #[test]
+    fn first_launch_passes_the_rate_limit() {
+        let profile = new_profile();
+        profile.check_rate_limit(&rate_limit(1), NOW).unwrap();
+    }
+
+    #[test]
+    fn launches_past_the_limit_are_rejected_within_the_window() {
+        let mut profile = new_profile();
+        profile.record_launch(Pubkey::new_unique(), NOW).unwrap();
+        profile.check_rate_limit(&rate_limit(2), NOW).unwrap();
+        profile
+            .record_launch(Pubkey::new_unique(), NOW + 10)
+            .unwrap();
+
+        let err = profile
+            .check_rate_limit(&rate_limit(2), NOW + 10)
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::LaunchRateLimited.into());
+        // a disabled limit lets everything through
+        profile.check_rate_limit(&rate_limit(0), NOW + 10).unwrap();
+    }
+
+    #[test]
+    fn launches_leave_the_window_once_it_has_fully_passed() {
+        let mut profile = new_profile();
+        profile.record_launch(Pubkey::new_unique(), NOW).unwrap();
+
+        let err = profile
+            .check_rate_limit(&rate_limit(1), NOW + 99)
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::LaunchRateLimited.into());
+        profile.check_rate_limit(&rate_limit(1), NOW + 100).unwrap();
+    }
+
+    #[test]
+    fn only_the_latest_launches_are_kept() {
+        let mut profile = new_profile();
+        for i in 0..=MAX_LAUNCHES_PER_WINDOW as i64 {
+            profile
+                .record_launch(Pubkey::new_unique(), NOW + i)
+                .unwrap();
+        }
+
+        assert_eq!(profile.launch_count, MAX_LAUNCHES_PER_WINDOW as u64 + 1);
+        assert_eq!(profile.recent_launch_ts.len(), MAX_LAUNCHES_PER_WINDOW);
+        assert_eq!(profile.recent_launch_ts[0], NOW + 1);
+    }
+}
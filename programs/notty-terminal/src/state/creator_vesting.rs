@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+/// Locks the creator's dev-buy, nothing unlocks before `cliff_ts` then the rest
+/// vests linearly from `start_ts` until `end_ts`
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorVesting {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub total_amount: u64,   // base units locked at launch
+    pub claimed_amount: u64, // base units already released to the creator
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+}
+
+impl CreatorVesting {
+    /// Base units unlocked at `now`, claimed or not
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now < self.cliff_ts {
+            return 0;
+        }
+        if now >= self.end_ts {
+            return self.total_amount;
+        }
+
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        ((self.total_amount as u128) * elapsed / duration) as u64
+    }
+
+    pub fn claimable_amount(&self, now: i64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.claimed_amount)
+    }
+
+    pub fn locked_amount(&self) -> u64 {
+        self.total_amount - self.claimed_amount
+    }
+}
+
+/// Vesting requested by the creator for the dev-buy, relative to the launch time
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct VestingSchedule {
+    pub cliff_seconds: i64,    // nothing can be claimed before launch + cliff
+    pub duration_seconds: i64, // everything is claimable at launch + duration
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1000 base units vesting over 100s, nothing before a 25s cliff
+    fn vesting() -> CreatorVesting {
+        CreatorVesting {
+            bump: 0,
+            mint: Pubkey::default(),
+            creator: Pubkey::default(),
+            total_amount: 1_000,
+            claimed_amount: 0,
+            start_ts: 1_000,
+            cliff_ts: 1_025,
+            end_ts: 1_100,
+        }
+    }
+
+    #[test]
+    fn nothing_vests_before_the_cliff() {
+        let vesting = vesting();
+        assert_eq!(vesting.vested_amount(1_000), 0);
+        assert_eq!(vesting.vested_amount(1_024), 0);
+        assert_eq!(vesting.claimable_amount(1_024), 0);
+    }
+
+    #[test]
+    fn vests_linearly_from_the_start_once_past_the_cliff() {
+        let vesting = vesting();
+        // the cliff releases everything vested since the start at once
+        assert_eq!(vesting.vested_amount(1_025), 250);
+        assert_eq!(vesting.vested_amount(1_050), 500);
+        // rounds down
+        assert_eq!(
+            CreatorVesting {
+                total_amount: 999,
+                ..vesting
+            }
+            .vested_amount(1_050),
+            499
+        );
+    }
+
+    #[test]
+    fn everything_vests_at_the_end() {
+        let vesting = vesting();
+        assert_eq!(vesting.vested_amount(1_099), 990);
+        assert_eq!(vesting.vested_amount(1_100), 1_000);
+        assert_eq!(vesting.vested_amount(i64::MAX), 1_000);
+    }
+
+    #[test]
+    fn claims_only_release_what_vested_since_the_last_one() {
+        let mut vesting = vesting();
+        vesting.claimed_amount = vesting.claimable_amount(1_050);
+        assert_eq!(vesting.claimed_amount, 500);
+        assert_eq!(vesting.locked_amount(), 500);
+
+        assert_eq!(vesting.claimable_amount(1_050), 0);
+        assert_eq!(vesting.claimable_amount(1_075), 250);
+        assert_eq!(vesting.claimable_amount(1_100), 500);
+    }
+}
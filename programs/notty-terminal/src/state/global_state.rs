@@ -1,17 +1,207 @@
 use anchor_lang::prelude::*;
 
-#[derive(InitSpace)]
+use crate::{
+    error::NottyTerminalError, InitializeArgs, MetadataPolicy, MigrationVenue,
+    DEFAULT_MAX_TOTAL_SUPPLY, DEFAULT_MIN_TOTAL_SUPPLY, DEFAULT_UNWIND_DELAY, MAX_AMM_CONFIGS,
+    MAX_LAUNCHES_PER_WINDOW, MAX_RATE_LIMIT_EXEMPTIONS,
+};
+
+/// Max launches per creator in a rolling window, `max_launches == 0` disables it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace, Debug)]
+pub struct LaunchRateLimit {
+    pub max_launches: u8,
+    pub window_seconds: i64,
+}
+
+impl LaunchRateLimit {
+    pub fn is_enabled(&self) -> bool {
+        self.max_launches > 0
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.is_enabled()
+                || (self.max_launches as usize <= MAX_LAUNCHES_PER_WINDOW
+                    && self.window_seconds > 0),
+            NottyTerminalError::InvalidRateLimit
+        );
+        Ok(())
+    }
+}
+
+#[derive(InitSpace, Default)]
 #[account]
 pub struct GlobalState {
     pub admin: Pubkey,
     pub vault: Pubkey,
     pub vault_bump: u8,
     pub bump: u8,
-    pub listing_fee_lamport: u64,   // 0.05 SOL token creation fee
+    pub listing_fee_lamport: u64, // 0.05 SOL token creation fee
+    /// Listing fee of creators holding an active `Verification`
+    pub verified_listing_fee_lamport: u64,
+    /// Share of the listing fee kept by the platform, the rest seeds the curve. 10000 = all
+    pub listing_fee_platform_bps: u16,
     pub trading_fee_bps: u16,       // 150 = 1.5% (basis points)
     pub migration_fee_lamport: u64, // 0.15 SOL for Raydium migration             // For bonding curve (if still needed)
     pub total_tokens_created: u64,
     pub total_fees_collected: u64,
     pub total_trading_volume: u64, // Track platform volume
     pub total_migrations: u64,     // Track successful migrations
+    pub allowed_venues: u8,        // bitmask of MigrationVenue::mask()
+    #[max_len(MAX_AMM_CONFIGS)]
+    pub allowed_amm_configs: Vec<Pubkey>, // Raydium CPMM fee tiers tokens may graduate into
+    pub rent_receiver: Pubkey,     // gets rent and dust swept when finalizing a migration
+    /// Renounce the freeze authority when minting instead of at graduation
+    pub revoke_freeze_at_creation: bool,
+    /// Limits on the name, symbol and uri of new tokens
+    pub metadata_policy: MetadataPolicy,
+    /// Bounds of a launch total supply, in whole tokens
+    pub min_total_supply: u64,
+    pub max_total_supply: u64,
+    pub launch_rate_limit: LaunchRateLimit,
+    /// Creators the launch rate limit doesn't apply to
+    #[max_len(MAX_RATE_LIMIT_EXEMPTIONS)]
+    pub rate_limit_exempt: Vec<Pubkey>,
+    /// seconds between an `emergency_unwind` and the first refund redemption
+    pub unwind_delay: i64,
+}
+
+impl GlobalState {
+    /// Fresh platform state with the default policies, used by `initialize` and when
+    /// upgrading a legacy account in `migrate_global_state`
+    pub fn new(
+        admin: Pubkey,
+        vault: Pubkey,
+        vault_bump: u8,
+        bump: u8,
+        args: &InitializeArgs,
+    ) -> Self {
+        GlobalState {
+            admin,
+            vault,
+            vault_bump,
+            bump,
+            listing_fee_lamport: args.listing_fee_lamport,
+            verified_listing_fee_lamport: args.listing_fee_lamport,
+            listing_fee_platform_bps: 5_000, // half to the platform, half seeds the curve
+            trading_fee_bps: args.trading_fee_bps,
+            migration_fee_lamport: args.migration_fee_lamport,
+            total_tokens_created: 0,
+            total_fees_collected: 0,
+            total_trading_volume: 0,
+            total_migrations: 0,
+            allowed_venues: MigrationVenue::RaydiumCpmm.mask(),
+            allowed_amm_configs: vec![args.amm_config],
+            rent_receiver: vault,
+            revoke_freeze_at_creation: false,
+            metadata_policy: MetadataPolicy::default(),
+            min_total_supply: DEFAULT_MIN_TOTAL_SUPPLY,
+            max_total_supply: DEFAULT_MAX_TOTAL_SUPPLY,
+            launch_rate_limit: LaunchRateLimit::default(),
+            rate_limit_exempt: vec![],
+            unwind_delay: DEFAULT_UNWIND_DELAY,
+        }
+    }
+
+    pub fn is_venue_allowed(&self, venue: MigrationVenue) -> bool {
+        self.allowed_venues & venue.mask() != 0
+    }
+
+    pub fn is_amm_config_allowed(&self, amm_config: &Pubkey) -> bool {
+        self.allowed_amm_configs.contains(amm_config)
+    }
+
+    pub fn is_rate_limit_exempt(&self, creator: &Pubkey) -> bool {
+        self.rate_limit_exempt.contains(creator)
+    }
+
+    pub fn listing_fee(&self, creator_verified: bool) -> u64 {
+        if creator_verified {
+            self.verified_listing_fee_lamport
+        } else {
+            self.listing_fee_lamport
+        }
+    }
+
+    /// Splits a listing fee into the platform cut and the curve seed
+    pub fn split_listing_fee(&self, listing_fee: u64) -> Result<(u64, u64)> {
+        let platform_fee = listing_fee
+            .checked_mul(self.listing_fee_platform_bps as u64)
+            .map(|fee| fee / 10_000)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        Ok((platform_fee, listing_fee - platform_fee))
+    }
+
+    pub fn is_total_supply_allowed(&self, total_supply: u64) -> bool {
+        (self.min_total_supply..=self.max_total_supply).contains(&total_supply)
+    }
+
+    /// Platform cut of a curve trade worth `amount` lamports
+    pub fn trading_fee(&self, amount: u64) -> Result<u64> {
+        amount
+            .checked_mul(self.trading_fee_bps as u64)
+            .and_then(|f| f.checked_div(10000))
+            .ok_or(error!(NottyTerminalError::NumericalOverflow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn global_state(listing_fee_platform_bps: u16) -> GlobalState {
+        GlobalState {
+            listing_fee_lamport: 20_000_000,
+            verified_listing_fee_lamport: 5_000_000,
+            listing_fee_platform_bps,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn listing_fee_splits_by_the_platform_bps() {
+        let fee = 20_000_000;
+        assert_eq!(
+            global_state(5_000).split_listing_fee(fee).unwrap(),
+            (10_000_000, 10_000_000)
+        );
+        assert_eq!(
+            global_state(2_500).split_listing_fee(fee).unwrap(),
+            (5_000_000, 15_000_000)
+        );
+        assert_eq!(
+            global_state(10_000).split_listing_fee(fee).unwrap(),
+            (fee, 0)
+        );
+        assert_eq!(global_state(0).split_listing_fee(fee).unwrap(), (0, fee));
+    }
+
+    #[test]
+    fn listing_fee_rounding_goes_to_the_curve() {
+        // 3333 * 1 / 10000 = 0.3333
+        assert_eq!(global_state(3_333).split_listing_fee(1).unwrap(), (0, 1));
+        // 3333 * 10 / 10000 = 3.333
+        assert_eq!(global_state(3_333).split_listing_fee(10).unwrap(), (3, 7));
+    }
+
+    #[test]
+    fn zero_listing_fee_splits_into_nothing() {
+        assert_eq!(global_state(5_000).split_listing_fee(0).unwrap(), (0, 0));
+        assert_eq!(global_state(10_000).split_listing_fee(0).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn verified_creators_get_their_own_listing_fee() {
+        let global_state = global_state(5_000);
+        assert_eq!(global_state.listing_fee(false), 20_000_000);
+        assert_eq!(global_state.listing_fee(true), 5_000_000);
+    }
+
+    #[test]
+    fn listing_fee_overflow_is_an_error() {
+        let err = global_state(10_000)
+            .split_listing_fee(u64::MAX)
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::NumericalOverflow.into());
+    }
 }
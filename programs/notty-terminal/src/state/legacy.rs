@@ -0,0 +1,338 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{
+    error::NottyTerminalError, GlobalState, InitializeArgs, MigrationAmounts, MigrationStage,
+    MigrationVenue, RefundPool, TokenState, DEFAULT_TOKEN_DECIMALS, TOKEN_STATE_VERSION,
+};
+
+/// `GlobalState` as deployed by 0.1.x, before the venue, policy and rate limit fields
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyGlobalState {
+    pub admin: Pubkey,
+    pub vault: Pubkey,
+    pub vault_bump: u8,
+    pub bump: u8,
+    pub listing_fee_lamport: u64,
+    pub trading_fee_bps: u16,
+    pub migration_fee_lamport: u64,
+    pub total_tokens_created: u64,
+    pub total_fees_collected: u64,
+    pub total_trading_volume: u64,
+    pub total_migrations: u64,
+}
+
+impl LegacyGlobalState {
+    pub const LEN: usize = 32 + 32 + 1 + 1 + 8 + 2 + 8 + 8 + 8 + 8 + 8;
+
+    pub fn try_from_account(account: &AccountInfo) -> Result<Self> {
+        read_legacy(
+            account,
+            GlobalState::DISCRIMINATOR,
+            Self::LEN,
+            GlobalState::INIT_SPACE,
+        )
+    }
+
+    /// Current layout with the defaults `initialize` would pick, keeping the fees and
+    /// platform counters
+    pub fn into_current(self, amm_config: Pubkey) -> GlobalState {
+        let args = InitializeArgs {
+            listing_fee_lamport: self.listing_fee_lamport,
+            trading_fee_bps: self.trading_fee_bps,
+            migration_fee_lamport: self.migration_fee_lamport,
+            amm_config,
+        };
+
+        GlobalState {
+            total_tokens_created: self.total_tokens_created,
+            total_fees_collected: self.total_fees_collected,
+            total_trading_volume: self.total_trading_volume,
+            total_migrations: self.total_migrations,
+            ..GlobalState::new(self.admin, self.vault, self.vault_bump, self.bump, &args)
+        }
+    }
+}
+
+/// `TokenState` as deployed by 0.1.x, every curve had 9 decimals and graduated into
+/// Raydium CPMM
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyTokenState {
+    pub bump: u8,
+    pub migrated: bool,
+    pub mint: Pubkey,
+    pub initial_price_per_token: u64,
+    pub sol_raised: u64,
+    pub tokens_sold: u64,
+    pub total_supply: u64,
+    pub sol_vault_bump: u8,
+    pub start_mcap: u64,
+    pub target_sol: u64,
+    pub raydium_pool: Option<Pubkey>,
+    pub migration_timestamp: i64,
+    pub creator: Pubkey,
+}
+
+impl LegacyTokenState {
+    pub const LEN: usize = 1 + 1 + 32 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + (1 + 32) + 8 + 32;
+
+    pub fn try_from_account(account: &AccountInfo) -> Result<Self> {
+        read_legacy(
+            account,
+            TokenState::DISCRIMINATOR,
+            Self::LEN,
+            TokenState::INIT_SPACE,
+        )
+    }
+
+    pub fn into_current(self) -> TokenState {
+        let migration_stage = if self.migrated {
+            MigrationStage::Completed
+        } else {
+            MigrationStage::Trading
+        };
+
+        TokenState {
+            bump: self.bump,
+            migrated: self.migrated,
+            mint: self.mint,
+            // 0.1.x stored the price per base unit, which truncated to 0 for most curves
+            initial_price_per_token: self
+                .start_mcap
+                .checked_div(self.total_supply)
+                .unwrap_or_default(),
+            sol_raised: self.sol_raised,
+            tokens_sold: self.tokens_sold,
+            total_supply: self.total_supply,
+            sol_vault_bump: self.sol_vault_bump,
+            start_mcap: self.start_mcap,
+            target_sol: self.target_sol,
+            pool: self.raydium_pool,
+            migration_timestamp: self.migration_timestamp,
+            creator: self.creator,
+            venue: MigrationVenue::RaydiumCpmm,
+            finalized: false,
+            migration_stage,
+            migration_amounts: MigrationAmounts::default(),
+            dev_buy_amount: 0,
+            dev_buy_locked_amount: 0,
+            dev_buy_cliff_ts: 0,
+            dev_buy_unlock_end_ts: 0,
+            decimals: DEFAULT_TOKEN_DECIMALS,
+            seed_liquidity: 0,
+            trading_starts_at: 0,
+            allowlist_root: [0; 32],
+            allowlist_ends_at: 0,
+            graduation_deadline: 0,
+            refund_pool: RefundPool::default(),
+            version: TOKEN_STATE_VERSION,
+        }
+    }
+}
+
+/// Decodes a program account still in its legacy layout, told apart from the current
+/// one by its size
+fn read_legacy<T: AnchorDeserialize>(
+    account: &AccountInfo,
+    discriminator: &[u8],
+    legacy_len: usize,
+    current_len: usize,
+) -> Result<T> {
+    require_keys_eq!(
+        *account.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+
+    let data = account.try_borrow_data()?;
+    require!(
+        data.len() != 8 + current_len,
+        NottyTerminalError::AccountAlreadyMigrated
+    );
+    require!(
+        data.len() == 8 + legacy_len && data.starts_with(discriminator),
+        NottyTerminalError::UnknownAccountLayout
+    );
+
+    Ok(T::deserialize(&mut &data[8..])?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_token_state(migrated: bool) -> LegacyTokenState {
+        LegacyTokenState {
+            bump: 254,
+            migrated,
+            mint: Pubkey::new_unique(),
+            initial_price_per_token: 50,
+            sol_raised: 120_000_000_000,
+            tokens_sold: 2_400_000_000_000_000,
+            total_supply: 1_000_000_000,
+            sol_vault_bump: 253,
+            start_mcap: 50_000_000_000,
+            target_sol: 450_000_000_000,
+            raydium_pool: migrated.then(Pubkey::new_unique),
+            migration_timestamp: 0,
+            creator: Pubkey::new_unique(),
+        }
+    }
+
+    fn legacy_global_state() -> LegacyGlobalState {
+        LegacyGlobalState {
+            admin: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            vault_bump: 255,
+            bump: 254,
+            listing_fee_lamport: 50_000_000,
+            trading_fee_bps: 150,
+            migration_fee_lamport: 150_000_000,
+            total_tokens_created: 12,
+            total_fees_collected: 3_000_000_000,
+            total_trading_volume: 900_000_000_000,
+            total_migrations: 2,
+        }
+    }
+
+    /// A curve account as 0.1.x wrote it, field by field in declaration order
+    fn legacy_token_state_bytes(mint: &Pubkey, creator: &Pubkey) -> Vec<u8> {
+        let mut data = TokenState::DISCRIMINATOR.to_vec();
+        data.push(254); // bump
+        data.push(0); // migrated
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(&0u64.to_le_bytes()); // initial_price_per_token
+        data.extend_from_slice(&120_000_000_000u64.to_le_bytes()); // sol_raised
+        data.extend_from_slice(&2_400_000_000_000_000u64.to_le_bytes()); // tokens_sold
+        data.extend_from_slice(&1_000_000_000u64.to_le_bytes()); // total_supply
+        data.push(253); // sol_vault_bump
+        data.extend_from_slice(&50_000_000_000u64.to_le_bytes()); // start_mcap
+        data.extend_from_slice(&450_000_000_000u64.to_le_bytes()); // target_sol
+        data.push(0); // raydium_pool: None
+        data.extend_from_slice(&0i64.to_le_bytes()); // migration_timestamp
+        data.extend_from_slice(creator.as_ref());
+        // the account was allocated for `Some(raydium_pool)`
+        data.resize(8 + LegacyTokenState::LEN, 0);
+        data
+    }
+
+    #[test]
+    fn serialized_legacy_token_state_upgrades() {
+        let key = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let mut data = legacy_token_state_bytes(&mint, &creator);
+
+        let mut lamports = 0;
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let token_state = LegacyTokenState::try_from_account(&account)
+            .unwrap()
+            .into_current();
+
+        assert_eq!(token_state.bump, 254);
+        assert_eq!(token_state.mint, mint);
+        assert_eq!(token_state.creator, creator);
+        assert_eq!(token_state.sol_raised, 120_000_000_000);
+        assert_eq!(token_state.tokens_sold, 2_400_000_000_000_000);
+        assert_eq!(token_state.sol_vault_bump, 253);
+        assert_eq!(token_state.target_sol, 450_000_000_000);
+        assert_eq!(token_state.pool, None);
+        assert_eq!(token_state.initial_price_per_token, 50);
+        assert_eq!(token_state.migration_stage, MigrationStage::Trading);
+
+        // the upgraded account serializes to the current layout and reads back
+        let mut upgraded = Vec::new();
+        token_state.try_serialize(&mut upgraded).unwrap();
+        assert!(upgraded.len() <= 8 + TokenState::INIT_SPACE);
+        let read_back = TokenState::try_deserialize(&mut upgraded.as_slice()).unwrap();
+        assert_eq!(read_back.tokens_sold, token_state.tokens_sold);
+        assert_eq!(read_back.version, TOKEN_STATE_VERSION);
+    }
+
+    #[test]
+    fn current_layout_is_already_migrated() {
+        let key = Pubkey::new_unique();
+        let mut data = Vec::new();
+        legacy_token_state(false)
+            .into_current()
+            .try_serialize(&mut data)
+            .unwrap();
+        data.resize(8 + TokenState::INIT_SPACE, 0);
+
+        let mut lamports = 0;
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let err = LegacyTokenState::try_from_account(&account).unwrap_err();
+        assert_eq!(err, NottyTerminalError::AccountAlreadyMigrated.into());
+    }
+
+    #[test]
+    fn legacy_lengths_match_the_serialized_layouts() {
+        let token_state = legacy_token_state(true);
+        assert_eq!(
+            token_state.try_to_vec().unwrap().len(),
+            LegacyTokenState::LEN
+        );
+
+        let global_state = legacy_global_state();
+        assert_eq!(
+            global_state.try_to_vec().unwrap().len(),
+            LegacyGlobalState::LEN
+        );
+    }
+
+    #[test]
+    fn legacy_curve_keeps_trading() {
+        let legacy = legacy_token_state(false);
+        let token_state = legacy.clone().into_current();
+
+        assert_eq!(token_state.migration_stage, MigrationStage::Trading);
+        assert_eq!(token_state.decimals, 9);
+        assert_eq!(token_state.version, TOKEN_STATE_VERSION);
+        assert_eq!(token_state.sol_raised, legacy.sol_raised);
+        assert_eq!(token_state.tokens_sold, legacy.tokens_sold);
+        assert_eq!(token_state.initial_price_per_token, 50);
+        assert!(token_state.is_trading_open());
+    }
+
+    #[test]
+    fn legacy_migrated_curve_is_completed() {
+        let legacy = legacy_token_state(true);
+        let token_state = legacy.clone().into_current();
+
+        assert_eq!(token_state.migration_stage, MigrationStage::Completed);
+        assert_eq!(token_state.pool, legacy.raydium_pool);
+        assert!(!token_state.is_trading_open());
+    }
+
+    #[test]
+    fn legacy_global_state_keeps_fees_and_counters() {
+        let legacy = legacy_global_state();
+        let amm_config = Pubkey::new_unique();
+        let global_state = legacy.clone().into_current(amm_config);
+
+        assert_eq!(global_state.admin, legacy.admin);
+        assert_eq!(global_state.trading_fee_bps, 150);
+        assert_eq!(global_state.total_tokens_created, 12);
+        assert_eq!(global_state.total_migrations, 2);
+        assert_eq!(global_state.rent_receiver, legacy.vault);
+        assert!(global_state.is_amm_config_allowed(&amm_config));
+        assert!(global_state.is_venue_allowed(MigrationVenue::RaydiumCpmm));
+    }
+}
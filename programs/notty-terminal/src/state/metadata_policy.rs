@@ -0,0 +1,275 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NottyTerminalError;
+
+/// Metaplex hard limits, the policy can only be stricter
+pub const MAX_NAME_LENGTH: u8 = 32;
+pub const MAX_SYMBOL_LENGTH: u8 = 10;
+pub const MAX_URI_LENGTH: u16 = 200;
+
+/// Where a token's off-chain metadata may be hosted
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace, Debug,
+)]
+pub enum UriScheme {
+    #[default]
+    Https,
+    Ipfs,
+    Arweave,
+}
+
+impl UriScheme {
+    pub const ALL: [UriScheme; 3] = [UriScheme::Https, UriScheme::Ipfs, UriScheme::Arweave];
+
+    /// Bit used for this scheme in `MetadataPolicy::allowed_uri_schemes`
+    pub fn mask(&self) -> u8 {
+        1 << (*self as u8)
+    }
+
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            UriScheme::Https => "https://",
+            UriScheme::Ipfs => "ipfs://",
+            UriScheme::Arweave => "ar://",
+        }
+    }
+}
+
+/// Characters a ticker can be made of
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace, Debug,
+)]
+pub enum SymbolCharset {
+    #[default]
+    UpperAlphanumeric, // A-Z and 0-9
+    Alphanumeric, // a-z, A-Z and 0-9
+}
+
+/// Admin limits on the name, symbol and uri a creator can launch with
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct MetadataPolicy {
+    pub max_name_length: u8,
+    pub max_symbol_length: u8,
+    pub max_uri_length: u16,
+    pub allowed_uri_schemes: u8, // bitmask of UriScheme::mask()
+    pub symbol_charset: SymbolCharset,
+    pub ascii_name_only: bool, // printable ASCII only, otherwise any non control character
+}
+
+impl Default for MetadataPolicy {
+    fn default() -> Self {
+        Self {
+            max_name_length: MAX_NAME_LENGTH,
+            max_symbol_length: MAX_SYMBOL_LENGTH,
+            max_uri_length: MAX_URI_LENGTH,
+            allowed_uri_schemes: UriScheme::ALL.iter().fold(0, |mask, s| mask | s.mask()),
+            symbol_charset: SymbolCharset::UpperAlphanumeric,
+            ascii_name_only: true,
+        }
+    }
+}
+
+impl MetadataPolicy {
+    /// Rejects policies Metaplex would not accept or that block every launch
+    pub fn validate_limits(&self) -> Result<()> {
+        require!(
+            self.max_name_length > 0 && self.max_name_length <= MAX_NAME_LENGTH,
+            NottyTerminalError::InvalidMetadataPolicy
+        );
+        require!(
+            self.max_symbol_length > 0 && self.max_symbol_length <= MAX_SYMBOL_LENGTH,
+            NottyTerminalError::InvalidMetadataPolicy
+        );
+        require!(
+            self.max_uri_length > 0 && self.max_uri_length <= MAX_URI_LENGTH,
+            NottyTerminalError::InvalidMetadataPolicy
+        );
+        require!(
+            self.allowed_uri_schemes != 0,
+            NottyTerminalError::InvalidMetadataPolicy
+        );
+        Ok(())
+    }
+
+    pub fn validate(&self, name: &str, symbol: &str, uri: &str) -> Result<()> {
+        self.validate_name(name)?;
+        self.validate_symbol(symbol)?;
+        self.validate_uri(uri)
+    }
+
+    fn validate_name(&self, name: &str) -> Result<()> {
+        require!(!name.trim().is_empty(), NottyTerminalError::EmptyName);
+        require!(
+            name.len() <= self.max_name_length as usize,
+            NottyTerminalError::NameTooLong
+        );
+
+        let valid = if self.ascii_name_only {
+            name.bytes().all(|b| b.is_ascii_graphic() || b == b' ')
+        } else {
+            !name.chars().any(char::is_control)
+        };
+        require!(valid, NottyTerminalError::InvalidNameCharacters);
+        Ok(())
+    }
+
+    pub fn validate_symbol(&self, symbol: &str) -> Result<()> {
+        require!(!symbol.is_empty(), NottyTerminalError::EmptySymbol);
+        require!(
+            symbol.len() <= self.max_symbol_length as usize,
+            NottyTerminalError::SymbolTooLong
+        );
+
+        let valid = match self.symbol_charset {
+            SymbolCharset::UpperAlphanumeric => symbol
+                .bytes()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit()),
+            SymbolCharset::Alphanumeric => symbol.bytes().all(|b| b.is_ascii_alphanumeric()),
+        };
+        require!(valid, NottyTerminalError::InvalidSymbolCharacters);
+        Ok(())
+    }
+
+    fn validate_uri(&self, uri: &str) -> Result<()> {
+        require!(
+            uri.len() <= self.max_uri_length as usize,
+            NottyTerminalError::UriTooLong
+        );
+        require!(
+            !uri.chars().any(|c| c.is_whitespace() || c.is_control()),
+            NottyTerminalError::InvalidUriScheme
+        );
+
+        let allowed = UriScheme::ALL.iter().any(|scheme| {
+            self.allowed_uri_schemes & scheme.mask() != 0
+                && uri.len() > scheme.prefix().len()
+                && uri.starts_with(scheme.prefix())
+        });
+        require!(allowed, NottyTerminalError::InvalidUriScheme);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URI: &str = "https://notty.fun/meta.json";
+
+    fn rejects(policy: &MetadataPolicy, name: &str, symbol: &str, uri: &str) -> Error {
+        policy.validate(name, symbol, uri).unwrap_err()
+    }
+
+    #[test]
+    fn default_policy_takes_the_metaplex_limits() {
+        let policy = MetadataPolicy::default();
+        policy.validate_limits().unwrap();
+
+        let name = "N".repeat(MAX_NAME_LENGTH as usize);
+        let symbol = "S".repeat(MAX_SYMBOL_LENGTH as usize);
+        let uri = format!("https://{}", "u".repeat(MAX_URI_LENGTH as usize - 8));
+        policy.validate(&name, &symbol, &uri).unwrap();
+
+        let err = rejects(&policy, &format!("{name}N"), "NOTTY", URI);
+        assert_eq!(err, NottyTerminalError::NameTooLong.into());
+        let err = rejects(&policy, "Notty", &format!("{symbol}S"), URI);
+        assert_eq!(err, NottyTerminalError::SymbolTooLong.into());
+        let err = rejects(&policy, "Notty", "NOTTY", &format!("{uri}u"));
+        assert_eq!(err, NottyTerminalError::UriTooLong.into());
+    }
+
+    #[test]
+    fn limits_are_counted_in_bytes() {
+        let policy = MetadataPolicy {
+            max_name_length: 4,
+            ascii_name_only: false,
+            ..Default::default()
+        };
+        // 3 chars, 7 bytes
+        let err = rejects(&policy, "éé€", "NOTTY", URI);
+        assert_eq!(err, NottyTerminalError::NameTooLong.into());
+        policy.validate("éé", "NOTTY", URI).unwrap();
+    }
+
+    #[test]
+    fn empty_fields_are_rejected() {
+        let policy = MetadataPolicy::default();
+        let err = rejects(&policy, "   ", "NOTTY", URI);
+        assert_eq!(err, NottyTerminalError::EmptyName.into());
+        let err = rejects(&policy, "Notty", "", URI);
+        assert_eq!(err, NottyTerminalError::EmptySymbol.into());
+        let err = rejects(&policy, "Notty", "NOTTY", "https://");
+        assert_eq!(err, NottyTerminalError::InvalidUriScheme.into());
+    }
+
+    #[test]
+    fn name_charset_follows_the_policy() {
+        let ascii = MetadataPolicy::default();
+        ascii.validate("Notty Terminal #1", "NOTTY", URI).unwrap();
+        let err = rejects(&ascii, "Nötty", "NOTTY", URI);
+        assert_eq!(err, NottyTerminalError::InvalidNameCharacters.into());
+        let err = rejects(&ascii, "Notty\tTerminal", "NOTTY", URI);
+        assert_eq!(err, NottyTerminalError::InvalidNameCharacters.into());
+
+        let unicode = MetadataPolicy {
+            ascii_name_only: false,
+            ..Default::default()
+        };
+        unicode.validate("Nötty", "NOTTY", URI).unwrap();
+        let err = rejects(&unicode, "Notty\nTerminal", "NOTTY", URI);
+        assert_eq!(err, NottyTerminalError::InvalidNameCharacters.into());
+    }
+
+    #[test]
+    fn symbol_charset_follows_the_policy() {
+        let upper = MetadataPolicy::default();
+        upper.validate("Notty", "NOTTY2", URI).unwrap();
+        for symbol in ["Notty", "NOT-TY", "NOT TY", "NÖTTY"] {
+            let err = rejects(&upper, "Notty", symbol, URI);
+            assert_eq!(err, NottyTerminalError::InvalidSymbolCharacters.into());
+        }
+
+        let mixed = MetadataPolicy {
+            symbol_charset: SymbolCharset::Alphanumeric,
+            ..Default::default()
+        };
+        mixed.validate("Notty", "nOtty2", URI).unwrap();
+        let err = rejects(&mixed, "Notty", "NOT_TY", URI);
+        assert_eq!(err, NottyTerminalError::InvalidSymbolCharacters.into());
+    }
+
+    #[test]
+    fn uri_must_use_an_allowed_scheme() {
+        let policy = MetadataPolicy {
+            allowed_uri_schemes: UriScheme::Ipfs.mask() | UriScheme::Arweave.mask(),
+            ..Default::default()
+        };
+        policy.validate("Notty", "NOTTY", "ipfs://bafy").unwrap();
+        policy.validate("Notty", "NOTTY", "ar://tx").unwrap();
+        for uri in [URI, "http://notty.fun", "ipfs://bafy meta", "IPFS://bafy"] {
+            let err = rejects(&policy, "Notty", "NOTTY", uri);
+            assert_eq!(err, NottyTerminalError::InvalidUriScheme.into());
+        }
+    }
+
+    #[test]
+    fn policies_outside_the_metaplex_limits_are_invalid() {
+        for policy in [
+            MetadataPolicy {
+                max_name_length: MAX_NAME_LENGTH + 1,
+                ..Default::default()
+            },
+            MetadataPolicy {
+                max_symbol_length: 0,
+                ..Default::default()
+            },
+            MetadataPolicy {
+                allowed_uri_schemes: 0,
+                ..Default::default()
+            },
+        ] {
+            let err = policy.validate_limits().unwrap_err();
+            assert_eq!(err, NottyTerminalError::InvalidMetadataPolicy.into());
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::NottyTerminalError, MIGRATION_PRICE_TOLERANCE_BPS};
+
+/// Checkpoint of a curve going through `prepare_migration` then `execute_migration`
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace, Debug,
+)]
+pub enum MigrationStage {
+    #[default]
+    Trading,
+    /// fee collected and liquidity staged in the curve vaults, trading is locked
+    Prepared,
+    /// pool created, mirrors `TokenState::migrated`
+    Completed,
+    /// graduation deadline missed or curve unwound by the admin, holders redeem their
+    /// tokens against the sol vault
+    Refunding,
+}
+
+/// Liquidity a curve graduates with, fixed when the migration is prepared
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace, Debug)]
+pub struct MigrationAmounts {
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub tokens_burned: u64, // unsold tokens above what the final curve price supports
+    pub price_per_million: u64, // final curve price the pool opens at
+}
+
+impl MigrationAmounts {
+    /// Opening pool price, `pool_sol` per `pool_curve_tokens`, must sit within
+    /// MIGRATION_PRICE_TOLERANCE_BPS of the curve price. `pool_curve_tokens` is the pool
+    /// token balance scaled with `TokenState::to_curve_units`
+    pub fn verify_price_continuity(&self, pool_curve_tokens: u64, pool_sol: u64) -> Result<()> {
+        // sol / tokens vs price / 1e6, cross multiplied to stay in integers
+        let pool_value = (pool_sol as u128) * 1_000_000;
+        let curve_value = (pool_curve_tokens as u128) * self.price_per_million as u128;
+
+        let deviation = pool_value.abs_diff(curve_value) * 10_000;
+        require!(
+            deviation <= curve_value * MIGRATION_PRICE_TOLERANCE_BPS as u128,
+            NottyTerminalError::PriceDiscontinuity
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amounts() -> MigrationAmounts {
+        MigrationAmounts {
+            token_amount: 1_000_000_000,
+            sol_amount: 450_000,
+            tokens_burned: 0,
+            price_per_million: 450,
+        }
+    }
+
+    #[test]
+    fn pool_at_the_curve_price_is_continuous() {
+        assert!(amounts()
+            .verify_price_continuity(1_000_000_000, 450_000)
+            .is_ok());
+        // within the 0.5% tolerance either way
+        assert!(amounts()
+            .verify_price_continuity(1_000_000_000, 452_000)
+            .is_ok());
+        assert!(amounts()
+            .verify_price_continuity(1_004_000_000, 450_000)
+            .is_ok());
+    }
+
+    #[test]
+    fn pool_off_the_curve_price_is_rejected() {
+        let err = amounts()
+            .verify_price_continuity(1_000_000_000, 460_000)
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::PriceDiscontinuity.into());
+
+        let err = amounts()
+            .verify_price_continuity(500_000_000, 450_000)
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::PriceDiscontinuity.into());
+    }
+}
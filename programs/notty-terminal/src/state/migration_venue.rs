@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+/// DEX a bonding curve graduates into once the migration threshold is reached
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace, Debug,
+)]
+pub enum MigrationVenue {
+    #[default]
+    RaydiumCpmm,
+    RaydiumAmmV4,
+    OrcaWhirlpool,
+    MeteoraDamm,
+}
+
+impl MigrationVenue {
+    /// Bit used for this venue in `GlobalState::allowed_venues`
+    pub fn mask(&self) -> u8 {
+        1 << (*self as u8)
+    }
+
+    /// Every venue takes spl token mints. Token-2022 launches (metadata pointer and token
+    /// metadata extensions) can only graduate into Raydium CPMM and Meteora DAMM v2,
+    /// Raydium AMM v4 and Orca Whirlpools are spl token only here
+    pub fn supports_token_program(&self, token_program: &Pubkey) -> bool {
+        *token_program == token::ID
+            || matches!(
+                self,
+                MigrationVenue::RaydiumCpmm | MigrationVenue::MeteoraDamm
+            )
+    }
+}
@@ -1,5 +1,29 @@
+pub mod allowlist;
+pub mod creator_launches;
+pub mod creator_profile;
+pub mod creator_vesting;
 pub mod global_state;
+pub mod legacy;
+pub mod metadata_policy;
+pub mod migration_stage;
+pub mod migration_venue;
+pub mod refund_pool;
+pub mod symbol_registry;
+pub mod token_profile;
 pub mod token_state;
+pub mod verification;
 
+pub use allowlist::*;
+pub use creator_launches::*;
+pub use creator_profile::*;
+pub use creator_vesting::*;
 pub use global_state::*;
+pub use legacy::*;
+pub use metadata_policy::*;
+pub use migration_stage::*;
+pub use migration_venue::*;
+pub use refund_pool::*;
+pub use symbol_registry::*;
+pub use token_profile::*;
 pub use token_state::*;
+pub use verification::*;
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NottyTerminalError;
+
+/// Why a curve went into refund
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace, Debug,
+)]
+pub enum RefundReason {
+    #[default]
+    GraduationDeadline,
+    /// admin shut the curve down with `emergency_unwind`
+    EmergencyUnwind,
+}
+
+/// What is left to redeem of a curve in refund, snapshotted when refunds open
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace, Debug)]
+pub struct RefundPool {
+    pub reason: RefundReason,
+    pub opens_at: i64, // redemptions are rejected before this timestamp
+    /// curve `sol_raised` and `tokens_sold` when trading was frozen
+    pub sol_raised: u64,
+    pub tokens_sold: u64,
+    pub lamports: u64, // sol vault balance above its rent exemption
+    pub tokens_outstanding: u64,
+}
+
+impl RefundPool {
+    pub fn is_open(&self, now: i64) -> bool {
+        now >= self.opens_at
+    }
+
+    /// Pro-rata lamports owed for returning `amount` base units, taken out of the pool
+    pub fn redeem(&mut self, amount: u64) -> Result<u64> {
+        require!(
+            amount > 0 && amount <= self.tokens_outstanding,
+            NottyTerminalError::InvalidAmount
+        );
+
+        let lamports = (self.lamports as u128)
+            .checked_mul(amount as u128)
+            .map(|res| res / self.tokens_outstanding as u128)
+            .ok_or(NottyTerminalError::NumericalOverflow)? as u64;
+        require!(lamports > 0, NottyTerminalError::NothingToRefund);
+
+        self.lamports -= lamports;
+        self.tokens_outstanding -= amount;
+
+        Ok(lamports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(lamports: u64, tokens_outstanding: u64) -> RefundPool {
+        RefundPool {
+            lamports,
+            tokens_outstanding,
+            tokens_sold: tokens_outstanding,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn redemptions_round_down_in_favor_of_the_pool() {
+        let mut refund_pool = pool(1_000, 3);
+
+        // 1000 / 3 = 333.33
+        assert_eq!(refund_pool.redeem(1).unwrap(), 333);
+        assert_eq!(refund_pool.lamports, 667);
+        assert_eq!(refund_pool.tokens_outstanding, 2);
+
+        let err = pool(1, 2).redeem(1).unwrap_err();
+        assert_eq!(err, NottyTerminalError::NothingToRefund.into());
+    }
+
+    #[test]
+    fn last_redeemer_drains_the_pool() {
+        let mut refund_pool = pool(1_000, 3);
+
+        let paid: u64 = (0..3).map(|_| refund_pool.redeem(1).unwrap()).sum();
+        // the rounding dust of earlier redemptions goes to the last one
+        assert_eq!(paid, 1_000);
+        assert_eq!(refund_pool.lamports, 0);
+        assert_eq!(refund_pool.tokens_outstanding, 0);
+    }
+
+    #[test]
+    fn redeeming_more_than_outstanding_is_rejected() {
+        let mut refund_pool = pool(1_000, 3);
+
+        let err = refund_pool.redeem(4).unwrap_err();
+        assert_eq!(err, NottyTerminalError::InvalidAmount.into());
+
+        let err = refund_pool.redeem(0).unwrap_err();
+        assert_eq!(err, NottyTerminalError::InvalidAmount.into());
+        assert_eq!(refund_pool.lamports, 1_000);
+        assert_eq!(refund_pool.tokens_outstanding, 3);
+    }
+}
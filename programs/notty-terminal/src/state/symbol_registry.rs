@@ -0,0 +1,126 @@
+use anchor_lang::{prelude::*, solana_program::hash::hash};
+
+use crate::{error::NottyTerminalError, MAX_SYMBOL_LENGTH};
+
+/// One per ticker, case insensitive. Claimed by the first token launched with the
+/// symbol, or reserved by the admin ahead of time for a known project
+#[account]
+#[derive(InitSpace)]
+pub struct SymbolRegistry {
+    pub bump: u8,
+    #[max_len(MAX_SYMBOL_LENGTH)]
+    pub symbol: String,
+    pub mint: Pubkey,                 // default until a token claims the symbol
+    pub creator: Pubkey,              // default until a token claims the symbol
+    pub reserved_for: Option<Pubkey>, // only this creator may claim the symbol
+    pub claimed_at: i64,
+}
+
+impl SymbolRegistry {
+    /// PDA seed of a ticker, hashed so any input derives an address before validation
+    pub fn seed(symbol: &str) -> [u8; 32] {
+        hash(symbol.to_ascii_uppercase().as_bytes()).to_bytes()
+    }
+
+    pub fn is_claimed(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
+
+    pub fn claim(
+        &mut self,
+        bump: u8,
+        symbol: &str,
+        mint: Pubkey,
+        creator: Pubkey,
+        now: i64,
+    ) -> Result<()> {
+        require!(!self.is_claimed(), NottyTerminalError::SymbolTaken);
+        if let Some(reserved_for) = self.reserved_for {
+            require!(reserved_for == creator, NottyTerminalError::SymbolReserved);
+        }
+
+        self.bump = bump;
+        self.symbol = symbol.to_string();
+        self.mint = mint;
+        self.creator = creator;
+        self.claimed_at = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // what `init_if_needed` hands to the first launch of a symbol
+    fn unclaimed() -> SymbolRegistry {
+        SymbolRegistry {
+            bump: 0,
+            symbol: String::new(),
+            mint: Pubkey::default(),
+            creator: Pubkey::default(),
+            reserved_for: None,
+            claimed_at: 0,
+        }
+    }
+
+    #[test]
+    fn symbols_are_case_insensitive() {
+        assert_eq!(SymbolRegistry::seed("NOTTY"), SymbolRegistry::seed("notty"));
+        assert_eq!(SymbolRegistry::seed("NOTTY"), SymbolRegistry::seed("NoTtY"));
+        assert_ne!(
+            SymbolRegistry::seed("NOTTY"),
+            SymbolRegistry::seed("NOTTY2")
+        );
+    }
+
+    #[test]
+    fn duplicate_symbols_are_rejected() {
+        let mut registry = unclaimed();
+        let (mint, creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+        registry.claim(255, "NOTTY", mint, creator, 100).unwrap();
+        assert!(registry.is_claimed());
+        assert_eq!(registry.claimed_at, 100);
+
+        let err = registry
+            .claim(
+                255,
+                "NOTTY",
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                200,
+            )
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::SymbolTaken.into());
+        // the same creator can't claim it twice either
+        let err = registry
+            .claim(255, "NOTTY", Pubkey::new_unique(), creator, 200)
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::SymbolTaken.into());
+        assert_eq!(registry.mint, mint);
+        assert_eq!(registry.claimed_at, 100);
+    }
+
+    #[test]
+    fn reserved_symbols_only_go_to_their_creator() {
+        let creator = Pubkey::new_unique();
+        let mut registry = SymbolRegistry {
+            reserved_for: Some(creator),
+            ..unclaimed()
+        };
+
+        let err = registry
+            .claim(
+                255,
+                "NOTTY",
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                100,
+            )
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::SymbolReserved.into());
+        registry
+            .claim(255, "NOTTY", Pubkey::new_unique(), creator, 100)
+            .unwrap();
+    }
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::{MAX_PROFILE_DESCRIPTION_LENGTH, MAX_PROFILE_URL_LENGTH};
+
+/// Description and social links of a token, read by front-ends instead of the
+/// off-chain json. Empty strings are unset links
+#[account]
+#[derive(InitSpace)]
+pub struct TokenProfile {
+    pub bump: u8,
+    pub mint: Pubkey,
+    #[max_len(MAX_PROFILE_DESCRIPTION_LENGTH)]
+    pub description: String,
+    #[max_len(MAX_PROFILE_URL_LENGTH)]
+    pub website: String,
+    #[max_len(MAX_PROFILE_URL_LENGTH)]
+    pub twitter: String,
+    #[max_len(MAX_PROFILE_URL_LENGTH)]
+    pub telegram: String,
+    pub image_hash: [u8; 32], // sha256 of the image behind the metadata uri
+    pub updated_at: i64,
+}
@@ -1,30 +1,529 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    error::NottyTerminalError, instructions::purchase_token::MIGRATION_THRESHOLD_PCT, integer_sqrt,
+    GlobalState, MigrationAmounts, MigrationStage, MigrationVenue, RefundPool, RefundReason,
+    CURVE_REFERENCE_BASE_UNITS,
+};
+
+use std::cmp::min;
+
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct TokenState {
     pub bump: u8,
     pub migrated: bool,
     pub mint: Pubkey,
-    pub initial_price_per_token: u64, // Will be 50 lamports
-    pub sol_raised: u64,
+    /// opening price in lamports per whole token, `start_mcap / total_supply`
+    pub initial_price_per_token: u64,
+    pub sol_raised: u64, // net lamports paid in by trades
     pub tokens_sold: u64,
-    pub total_supply: u64,
+    pub total_supply: u64, // whole tokens
     pub sol_vault_bump: u8,
     pub start_mcap: u64, // 50 SOL in lamports
     pub target_sol: u64, // 450 SOL in lamports (migration trigger)
-    pub raydium_pool: Option<Pubkey>,
+    /// pool created at migration, whichever venue
+    pub pool: Option<Pubkey>,
     pub migration_timestamp: i64,
     pub creator: Pubkey,
+    pub venue: MigrationVenue,
+    pub finalized: bool, // curve vaults closed after migration
+    pub migration_stage: MigrationStage,
+    /// amounts staged by `prepare_migration`, consumed by `execute_migration`
+    pub migration_amounts: MigrationAmounts,
+    /// base units the creator bought in `create_token`
+    pub dev_buy_amount: u64,
+    /// dev-buy base units still held by the `CreatorVesting` account
+    pub dev_buy_locked_amount: u64,
+    pub dev_buy_cliff_ts: i64,
+    pub dev_buy_unlock_end_ts: i64,
+    pub decimals: u8,
+    /// part of the listing fee put in the sol vault at launch, pool liquidity but not
+    /// counted in `sol_raised`
+    pub seed_liquidity: u64,
+    /// buys and sells are rejected before this timestamp, the dev-buy isn't
+    pub trading_starts_at: i64,
+    /// root of the allowlisted buyers, only meaningful before `allowlist_ends_at`
+    pub allowlist_root: [u8; 32],
+    pub allowlist_ends_at: i64, // 0 without an allowlist phase
+    /// curve must graduate before this timestamp or it goes into refund, 0 = no deadline
+    pub graduation_deadline: i64,
+    /// set by `open_refunds` or `emergency_unwind`, drained by `redeem_refund`
+    pub refund_pool: RefundPool,
+    pub version: u8, // TOKEN_STATE_VERSION
 }
 
 impl TokenState {
+    /// Buys and sells are locked as soon as a migration is prepared
+    pub fn is_trading_open(&self) -> bool {
+        !self.migrated && self.migration_stage == MigrationStage::Trading
+    }
+
+    pub fn has_trading_started(&self, now: i64) -> bool {
+        now >= self.trading_starts_at
+    }
+
+    pub fn is_allowlist_phase(&self, now: i64) -> bool {
+        now < self.allowlist_ends_at
+    }
+
+    pub fn is_past_graduation_deadline(&self, now: i64) -> bool {
+        self.graduation_deadline != 0 && now >= self.graduation_deadline
+    }
+
+    /// Stops trading and migration for good and sets `lamports` aside for the current
+    /// holders, redeemable from `opens_at`. The dev-buy still locked in vesting is
+    /// forfeited, it can't be claimed anymore and takes no share of the pool
+    pub fn open_refunds(
+        &mut self,
+        reason: RefundReason,
+        lamports: u64,
+        opens_at: i64,
+    ) -> RefundPool {
+        self.migration_stage = MigrationStage::Refunding;
+        self.refund_pool = RefundPool {
+            reason,
+            opens_at,
+            sol_raised: self.sol_raised,
+            tokens_sold: self.tokens_sold,
+            lamports,
+            tokens_outstanding: self.tokens_sold.saturating_sub(self.dev_buy_locked_amount),
+        };
+        self.refund_pool
+    }
+
     pub fn check_migration_ready(&self) -> bool {
         self.sol_raised >= self.target_sol
     }
 
+    /// Base units in one whole token
+    pub fn base_units_per_token(&self) -> Result<u64> {
+        10u64
+            .checked_pow(self.decimals as u32)
+            .ok_or(error!(NottyTerminalError::NumericalOverflow))
+    }
+
+    pub fn total_base_units(&self) -> Result<u64> {
+        self.total_supply
+            .checked_mul(self.base_units_per_token()?)
+            .ok_or(error!(NottyTerminalError::NumericalOverflow))
+    }
+
+    /// Scales `amount` base units of this token to CURVE_REFERENCE_BASE_UNITS, the unit
+    /// curve prices are quoted in. Identity for a 1B supply with 9 decimals
+    pub fn to_curve_units(&self, amount: u64) -> Result<u64> {
+        let total_base_units = self.total_base_units()? as u128;
+        let curve_units = (amount as u128)
+            .checked_mul(CURVE_REFERENCE_BASE_UNITS as u128)
+            .map(|res| res / total_base_units)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        u64::try_from(curve_units).map_err(|_| error!(NottyTerminalError::NumericalOverflow))
+    }
+
+    pub fn from_curve_units(&self, curve_units: u128) -> Result<u64> {
+        let amount = curve_units
+            .checked_mul(self.total_base_units()? as u128)
+            .map(|res| res / CURVE_REFERENCE_BASE_UNITS as u128)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        u64::try_from(amount).map_err(|_| error!(NottyTerminalError::NumericalOverflow))
+    }
+
+    /// Share of the migration threshold `tokens_sold` represents, out of `scale`
+    /// and capped at it. Computed in u128, `tokens_sold * scale` overflows u64
+    pub fn curve_progress(&self, tokens_sold: u64, scale: u64) -> Result<u64> {
+        let total_base_units = self.total_base_units()?;
+        let migration_base_units = (total_base_units / 100) * MIGRATION_THRESHOLD_PCT;
+
+        let progress = (tokens_sold as u128)
+            .checked_mul(scale as u128)
+            .map(|res| res / migration_base_units as u128)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        Ok(min(progress, scale as u128) as u64)
+    }
+
+    /// Spot price of the curve in lamports per million base units
+    pub fn current_price_per_million(&self) -> Result<u64> {
+        self.price_per_million_at(self.tokens_sold)
+    }
+
+    /// Price of the curve in lamports per million base units once `tokens_sold` are sold
+    pub fn price_per_million_at(&self, tokens_sold: u64) -> Result<u64> {
+        const BASE_PRICE_PER_MILLION: u64 = 50;
+        const MAX_PRICE_PER_MILLION: u64 = 450;
+        const PRICE_RANGE: u64 = MAX_PRICE_PER_MILLION - BASE_PRICE_PER_MILLION;
+
+        // Scale to make progress more sensitive
+        // Using basis points (10000 = 100%) for better precision
+        let progress_bps = self.curve_progress(tokens_sold, 10000)?;
+
+        // Square root of basis points (0-10000 becomes 0-100)
+        let sqrt_progress = integer_sqrt(progress_bps)?;
+
+        // Price increases from base to max (sqrt of 10000 = 100)
+        Ok(BASE_PRICE_PER_MILLION + (PRICE_RANGE * sqrt_progress / 100))
+    }
+
+    /// Lamports paid to the curve (fees excluded) for `amount_base_units` at the spot price
+    pub fn purchase_cost(&self, amount_base_units: u64) -> Result<u64> {
+        self.cost_at(self.tokens_sold, amount_base_units)
+    }
+
+    /// Lamports paid out of the curve (fees excluded) for selling `amount_base_units`
+    /// back. Priced like `purchase_cost` at the spot price once they are returned, so a
+    /// buy sold straight back pays out exactly what it cost
+    pub fn sale_proceeds(&self, amount_base_units: u64) -> Result<u64> {
+        let tokens_sold = self
+            .tokens_sold
+            .checked_sub(amount_base_units)
+            .ok_or(NottyTerminalError::InsufficientTokensSold)?;
+        self.cost_at(tokens_sold, amount_base_units)
+    }
+
+    fn cost_at(&self, tokens_sold: u64, amount_base_units: u64) -> Result<u64> {
+        let price_per_million = self.price_per_million_at(tokens_sold)?;
+
+        (self.to_curve_units(amount_base_units)? / 1_000_000)
+            .checked_mul(price_per_million)
+            .ok_or(error!(NottyTerminalError::NumericalOverflow))
+    }
+
+    /// Books a sale paying `base_proceeds`. They come out of `sol_raised` first, spot
+    /// pricing can pay a seller more than the trades left in it, and the rest out of
+    /// the seeded liquidity sitting in the same vault
+    pub fn record_sale(&mut self, amount_base_units: u64, base_proceeds: u64) -> Result<()> {
+        self.tokens_sold = self
+            .tokens_sold
+            .checked_sub(amount_base_units)
+            .ok_or(NottyTerminalError::InsufficientTokensSold)?;
+
+        let from_raised = min(base_proceeds, self.sol_raised);
+        self.sol_raised -= from_raised;
+        self.seed_liquidity = self
+            .seed_liquidity
+            .checked_sub(base_proceeds - from_raised)
+            .ok_or(NottyTerminalError::InsufficientVaultBalance)?;
+
+        Ok(())
+    }
+
+    /// Every check `prepare_migration` runs before touching the vaults. Returns the
+    /// amounts the pool will be seeded with
+    pub fn validate_migration_prepare(
+        &self,
+        global_state: &GlobalState,
+        launch_token_program: &Pubkey,
+        now: i64,
+    ) -> Result<MigrationAmounts> {
+        require!(!self.migrated, NottyTerminalError::AlreadyMigrated);
+
+        require!(
+            self.migration_stage == MigrationStage::Trading,
+            NottyTerminalError::MigrationAlreadyPrepared
+        );
+
+        require!(
+            !self.is_past_graduation_deadline(now),
+            NottyTerminalError::GraduationDeadlinePassed
+        );
+
+        require!(
+            global_state.is_venue_allowed(self.venue),
+            NottyTerminalError::VenueNotAllowed
+        );
+
+        require!(
+            self.venue.supports_token_program(launch_token_program),
+            NottyTerminalError::UnsupportedTokenProgram
+        );
+
+        require!(
+            self.check_migration_ready(),
+            NottyTerminalError::TargetNotReached
+        );
+
+        self.price_continuous_amounts()
+    }
+
+    /// Every check a venue runs before spending the checkpointed liquidity
+    pub fn validate_migration_execute(
+        &self,
+        venue: MigrationVenue,
+        global_state: &GlobalState,
+    ) -> Result<()> {
+        require!(!self.migrated, NottyTerminalError::AlreadyMigrated);
+
+        require!(
+            self.migration_stage == MigrationStage::Prepared,
+            NottyTerminalError::MigrationNotPrepared
+        );
+
+        require!(self.venue == venue, NottyTerminalError::WrongVenue);
+
+        require!(
+            global_state.is_venue_allowed(venue),
+            NottyTerminalError::VenueNotAllowed
+        );
+
+        Ok(())
+    }
+
+    /// Splits the unsold supply and the raised SOL so that sol_amount / token_amount
+    /// matches the curve's terminal spot price. Every lamport raised goes to the pool,
+    /// unsold tokens above what it pairs with are burned.
+    pub fn price_continuous_amounts(&self) -> Result<MigrationAmounts> {
+        let (available_tokens, token_amount, sol_amount, price_per_million) = self.pool_split()?;
+
+        require!(
+            token_amount <= available_tokens,
+            NottyTerminalError::PoolCapacityExceeded
+        );
+        require!(
+            token_amount > 0 && sol_amount > 0,
+            NottyTerminalError::InvalidAmount
+        );
+
+        Ok(MigrationAmounts {
+            token_amount,
+            sol_amount,
+            tokens_burned: available_tokens - token_amount,
+            price_per_million,
+        })
+    }
+
+    /// Purchases can't raise more SOL than the unsold supply pairs with at the spot
+    /// price, the pool would otherwise open above the curve
+    pub fn check_pool_capacity(&self) -> Result<()> {
+        let (available_tokens, token_amount, _, _) = self.pool_split()?;
+        require!(
+            token_amount <= available_tokens,
+            NottyTerminalError::PoolCapacityExceeded
+        );
+        Ok(())
+    }
+
+    /// Unsold base units, the pool SOL and the base units pairing with it at the spot
+    /// price, as `(available_tokens, token_amount, sol_amount, price_per_million)`
+    fn pool_split(&self) -> Result<(u64, u64, u64, u64)> {
+        let available_tokens = self
+            .total_base_units()?
+            .checked_sub(self.tokens_sold)
+            .ok_or(NottyTerminalError::InsufficientVaultBalance)?;
+        // the seeded listing fee goes to the pool too
+        let sol_amount = self
+            .sol_raised
+            .checked_add(self.seed_liquidity)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        let price_per_million = self.current_price_per_million()?;
+
+        let curve_token_amount = (sol_amount as u128)
+            .checked_mul(1_000_000)
+            .map(|res| res / price_per_million as u128)
+            .ok_or(NottyTerminalError::NumericalOverflow)?;
+        let token_amount = self.from_curve_units(curve_token_amount)?;
+
+        Ok((
+            available_tokens,
+            token_amount,
+            sol_amount,
+            price_per_million,
+        ))
+    }
+
     pub fn get_progress_percentage(&self) -> u8 {
-        let pct = (self.tokens_sold * 100) / self.total_supply;
-        std::cmp::min(pct as u8, 100)
+        let total_base_units = self.total_base_units().unwrap_or(u64::MAX).max(1);
+        let pct = (self.tokens_sold as u128 * 100) / total_base_units as u128;
+        min(pct, 100) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token;
+
+    const TARGET_SOL: u64 = 450_000_000_000;
+
+    fn fresh_curve() -> TokenState {
+        TokenState {
+            total_supply: 1_000_000_000,
+            decimals: 9,
+            target_sol: TARGET_SOL,
+            ..Default::default()
+        }
+    }
+
+    fn global_state() -> GlobalState {
+        GlobalState {
+            allowed_venues: MigrationVenue::RaydiumCpmm.mask(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn spot_price_spans_the_curve_without_overflowing() {
+        let mut token_state = fresh_curve();
+        assert_eq!(token_state.current_price_per_million().unwrap(), 50);
+
+        token_state.tokens_sold = token_state.total_base_units().unwrap();
+        assert_eq!(token_state.current_price_per_million().unwrap(), 450);
+    }
+
+    #[test]
+    fn buying_then_selling_back_pays_out_the_cost() {
+        let mut token_state = TokenState {
+            sol_raised: 40_000_000_000,
+            tokens_sold: 200_000_000_000_000_000,
+            seed_liquidity: 1_000_000_000,
+            ..fresh_curve()
+        };
+        let amount = 50_000_000_000_000_000;
+
+        let cost = token_state.purchase_cost(amount).unwrap();
+        token_state.tokens_sold += amount;
+        token_state.sol_raised += cost;
+
+        let proceeds = token_state.sale_proceeds(amount).unwrap();
+        assert_eq!(proceeds, cost);
+
+        token_state.record_sale(amount, proceeds).unwrap();
+        assert_eq!(token_state.sol_raised, 40_000_000_000);
+        assert_eq!(token_state.tokens_sold, 200_000_000_000_000_000);
+        assert_eq!(token_state.seed_liquidity, 1_000_000_000);
+    }
+
+    #[test]
+    fn sale_shortfall_comes_out_of_the_seed_liquidity() {
+        let mut token_state = TokenState {
+            sol_raised: 600,
+            tokens_sold: 1_000,
+            seed_liquidity: 500,
+            ..fresh_curve()
+        };
+
+        token_state.record_sale(400, 900).unwrap();
+        assert_eq!(token_state.sol_raised, 0);
+        assert_eq!(token_state.seed_liquidity, 200);
+        assert_eq!(token_state.tokens_sold, 600);
+
+        let err = token_state.record_sale(100, 201).unwrap_err();
+        assert_eq!(err, NottyTerminalError::InsufficientVaultBalance.into());
+    }
+
+    #[test]
+    fn selling_more_than_was_sold_fails() {
+        let token_state = TokenState {
+            tokens_sold: 1_000,
+            ..fresh_curve()
+        };
+        let err = token_state.sale_proceeds(1_001).unwrap_err();
+        assert_eq!(err, NottyTerminalError::InsufficientTokensSold.into());
+    }
+
+    #[test]
+    fn prepare_fails_on_a_fresh_curve() {
+        let err = fresh_curve()
+            .validate_migration_prepare(&global_state(), &token::ID, 0)
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::TargetNotReached.into());
+    }
+
+    #[test]
+    fn prepare_fails_below_target_sol() {
+        let token_state = TokenState {
+            sol_raised: TARGET_SOL - 1,
+            tokens_sold: 8_000_000_000_000_000,
+            ..fresh_curve()
+        };
+        let err = token_state
+            .validate_migration_prepare(&global_state(), &token::ID, 0)
+            .unwrap_err();
+        assert_eq!(err, NottyTerminalError::TargetNotReached.into());
+    }
+
+    #[test]
+    fn prepare_passes_once_target_sol_is_raised() {
+        let token_state = TokenState {
+            sol_raised: TARGET_SOL,
+            tokens_sold: 8_000_000_000_000_000,
+            ..fresh_curve()
+        };
+        let amounts = token_state
+            .validate_migration_prepare(&global_state(), &token::ID, 0)
+            .unwrap();
+        assert!(amounts.token_amount > 0);
+        assert_eq!(amounts.sol_amount, TARGET_SOL);
+    }
+
+    #[test]
+    fn progress_percentage_does_not_overflow_on_large_supplies() {
+        let token_state = TokenState {
+            tokens_sold: 600_000_000_000_000_000,
+            ..fresh_curve()
+        };
+        assert_eq!(token_state.get_progress_percentage(), 60);
+
+        let sold_out = TokenState {
+            tokens_sold: 1_000_000_000_000_000_000,
+            ..fresh_curve()
+        };
+        assert_eq!(sold_out.get_progress_percentage(), 100);
+    }
+
+    #[test]
+    fn pool_takes_every_lamport_raised() {
+        let token_state = TokenState {
+            sol_raised: TARGET_SOL,
+            seed_liquidity: 1_000_000_000,
+            tokens_sold: 8_000_000_000_000_000,
+            ..fresh_curve()
+        };
+        let amounts = token_state.price_continuous_amounts().unwrap();
+        assert_eq!(amounts.sol_amount, TARGET_SOL + 1_000_000_000);
+        assert!(token_state.check_pool_capacity().is_ok());
+    }
+
+    #[test]
+    fn raising_more_than_the_unsold_supply_pairs_with_cannot_migrate() {
+        // 90% sold at the max price leaves 100M tokens, worth 45k SOL
+        let token_state = TokenState {
+            sol_raised: 50_000_000_000_000,
+            tokens_sold: 900_000_000_000_000_000,
+            ..fresh_curve()
+        };
+        let err = token_state.price_continuous_amounts().unwrap_err();
+        assert_eq!(err, NottyTerminalError::PoolCapacityExceeded.into());
+    }
+
+    #[test]
+    fn purchases_past_the_pool_capacity_are_rejected() {
+        let at_capacity = TokenState {
+            sol_raised: 45_000_000_000_000,
+            tokens_sold: 900_000_000_000_000_000,
+            ..fresh_curve()
+        };
+        assert!(at_capacity.check_pool_capacity().is_ok());
+
+        let over_capacity = TokenState {
+            sol_raised: 45_000_000_000_001,
+            ..at_capacity
+        };
+        let err = over_capacity.check_pool_capacity().unwrap_err();
+        assert_eq!(err, NottyTerminalError::PoolCapacityExceeded.into());
+    }
+
+    #[test]
+    fn refunds_leave_the_locked_dev_buy_out() {
+        let mut token_state = TokenState {
+            sol_raised: 10_000,
+            tokens_sold: 1_000,
+            dev_buy_amount: 400,
+            dev_buy_locked_amount: 300,
+            ..fresh_curve()
+        };
+
+        let refund_pool = token_state.open_refunds(RefundReason::GraduationDeadline, 9_000, 0);
+        assert_eq!(refund_pool.tokens_sold, 1_000);
+        assert_eq!(refund_pool.tokens_outstanding, 700);
+        assert_eq!(token_state.migration_stage, MigrationStage::Refunding);
     }
 }
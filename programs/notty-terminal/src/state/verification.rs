@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+/// What a `Verification` vouches for
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace, Debug,
+)]
+pub enum VerificationKind {
+    #[default]
+    Creator,
+    Token,
+}
+
+/// Admin-issued badge of a creator wallet or a launch mint, seeded by that pubkey
+#[account]
+#[derive(InitSpace)]
+pub struct Verification {
+    pub bump: u8,
+    pub subject: Pubkey, // creator wallet or mint
+    pub kind: VerificationKind,
+    pub reason_code: u16, // off-chain meaning, e.g. KYC, known team, partner
+    pub verified_by: Pubkey,
+    pub verified_at: i64,
+    pub expires_at: i64, // verification lapses at this timestamp
+}
+
+impl Verification {
+    pub fn is_active(&self, now: i64) -> bool {
+        now < self.expires_at
+    }
+
+    /// Active badge of a creator wallet, the one the verified listing fee goes by
+    pub fn is_active_creator(&self, now: i64) -> bool {
+        self.kind == VerificationKind::Creator && self.is_active(now)
+    }
+}
@@ -0,0 +1,80 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  admin,
+  CPMM_AMM_CONFIG,
+  ensureGlobalState,
+  fundedWallet,
+  globalStatePda,
+  program,
+} from "./setup";
+
+describe("admin", () => {
+  before(ensureGlobalState);
+
+  it("rejects a second initialize", async () => {
+    const attacker = await fundedWallet(5);
+
+    try {
+      await program.methods
+        .initialize({
+          listingFeeLamport: new anchor.BN(0),
+          tradingFeeBps: 0,
+          migrationFeeLamport: new anchor.BN(0),
+          ammConfig: CPMM_AMM_CONFIG,
+        })
+        .accounts({ admin: attacker.publicKey })
+        .signers([attacker])
+        .rpc();
+      expect.fail("initialize should have been rejected");
+    } catch (err) {
+      // the system program refuses to create the global state a second time
+      expect(String(err)).to.match(/already in use/);
+    }
+
+    const globalState = await program.account.globalState.fetch(
+      globalStatePda()
+    );
+    expect(globalState.admin.equals(admin.publicKey)).to.be.true;
+  });
+
+  it("rejects update_global_state from anyone but the admin", async () => {
+    const attacker = await fundedWallet(5);
+
+    try {
+      await program.methods
+        .updateGlobalState({
+          allowedVenues: null,
+          allowedAmmConfigs: [attacker.publicKey],
+          rentReceiver: null,
+          revokeFreezeAtCreation: null,
+          metadataPolicy: null,
+          minTotalSupply: null,
+          maxTotalSupply: null,
+          verifiedListingFeeLamport: null,
+          listingFeePlatformBps: null,
+          launchRateLimit: null,
+          rateLimitExempt: null,
+          unwindDelay: null,
+        })
+        .accounts({ admin: attacker.publicKey })
+        .signers([attacker])
+        .rpc();
+      expect.fail("update_global_state should have been rejected");
+    } catch (err) {
+      expect(err).to.be.instanceOf(anchor.AnchorError);
+      expect((err as anchor.AnchorError).error.errorCode.code).to.equal(
+        "UnauthorizedAdmin"
+      );
+    }
+
+    const globalState = await program.account.globalState.fetch(
+      globalStatePda()
+    );
+    expect(
+      globalState.allowedAmmConfigs.some((config) =>
+        config.equals(attacker.publicKey)
+      )
+    ).to.be.false;
+  });
+});
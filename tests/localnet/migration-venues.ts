@@ -0,0 +1,544 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  createAccount,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  NATIVE_MINT,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  computeUnits,
+  connection,
+  CPMM_AMM_CONFIG,
+  launchToTarget,
+  pda,
+  poolCreatorPda,
+  prepareMigration,
+  program,
+  sendWithLookupTable,
+  sortMints,
+  tokenStatePda,
+} from "./setup";
+
+// devnet deployments, cloned by [test.validator] in Anchor.toml
+const RAYDIUM_CPMM_PROGRAM_ID = new PublicKey(
+  "CPMDWBwJDtYax9qW7AyRuVC19Cc4L4Vcy4n2BHAbHkCW"
+);
+const RAYDIUM_AMM_V4_PROGRAM_ID = new PublicKey(
+  "HWy1jotHpo6UqeQxx49dpYYdQB8wj9Qk9MdxwjLvDHB8"
+);
+const RAYDIUM_AMM_V4_FEE_DESTINATION = new PublicKey(
+  "3XMrhbv989VxAMi3DErLV9eJht1pHppW5LbKxe9fkEFR"
+);
+const OPENBOOK_PROGRAM_ID = new PublicKey(
+  "EoTcMgcDRTJVZDMZWBoU6rhYHZfkNTVEAfz3uUJRcYGj"
+);
+const ORCA_WHIRLPOOL_PROGRAM_ID = new PublicKey(
+  "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"
+);
+const ORCA_WHIRLPOOLS_CONFIG = new PublicKey(
+  "FcrweFY1G9HJAHG5inkGB6pKg1HZ6x9UC2WioAfWrGkR"
+);
+const METEORA_DAMM_PROGRAM_ID = new PublicKey(
+  "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG"
+);
+
+const ORCA_TICK_SPACING = 64;
+const ORCA_MAX_TICK_INDEX = 443_636;
+const ORCA_TICK_ARRAY_SIZE = 88;
+
+async function expectMigrated(
+  mint: PublicKey,
+  pool: PublicKey,
+  venueProgram: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+) {
+  const tokenState = await program.account.tokenState.fetch(
+    tokenStatePda(mint)
+  );
+  expect(tokenState.migrated).to.be.true;
+  expect(tokenState.migrationStage).to.deep.equal({ completed: {} });
+  expect(tokenState.pool.equals(pool)).to.be.true;
+
+  const poolAccount = await connection.getAccountInfo(pool);
+  expect(poolAccount.owner.equals(venueProgram)).to.be.true;
+
+  // nobody can mint or freeze holders once the token trades on the venue
+  const mintAccount = await getMint(
+    connection,
+    mint,
+    "confirmed",
+    tokenProgram
+  );
+  expect(mintAccount.mintAuthority).to.be.null;
+  expect(mintAccount.freezeAuthority).to.be.null;
+}
+
+// Creates the OpenBook market a Raydium AMM v4 pool trades against, the launch
+// token is the base (coin) side and WSOL the quote (pc) side
+async function createOpenBookMarket(payer: Keypair, baseMint: PublicKey) {
+  const market = Keypair.generate();
+  const requestQueue = Keypair.generate();
+  const eventQueue = Keypair.generate();
+  const bids = Keypair.generate();
+  const asks = Keypair.generate();
+
+  let vaultSignerNonce = 0;
+  let vaultSigner: PublicKey;
+  for (; ; vaultSignerNonce++) {
+    try {
+      const nonce = Buffer.alloc(8);
+      nonce.writeUInt32LE(vaultSignerNonce, 0);
+      vaultSigner = PublicKey.createProgramAddressSync(
+        [market.publicKey.toBuffer(), nonce],
+        OPENBOOK_PROGRAM_ID
+      );
+      break;
+    } catch (err) {
+      // on curve, try the next nonce
+    }
+  }
+
+  const baseVault = await createAccount(
+    connection,
+    payer,
+    baseMint,
+    vaultSigner,
+    Keypair.generate()
+  );
+  const quoteVault = await createAccount(
+    connection,
+    payer,
+    NATIVE_MINT,
+    vaultSigner,
+    Keypair.generate()
+  );
+
+  const createAccounts = new Transaction();
+  for (const [account, space] of [
+    [market, 388],
+    [requestQueue, 5120 + 12],
+    [eventQueue, 262144 + 12],
+    [bids, 65536 + 12],
+    [asks, 65536 + 12],
+  ] as [Keypair, number][]) {
+    createAccounts.add(
+      SystemProgram.createAccount({
+        fromPubkey: payer.publicKey,
+        newAccountPubkey: account.publicKey,
+        lamports: await connection.getMinimumBalanceForRentExemption(space),
+        space,
+        programId: OPENBOOK_PROGRAM_ID,
+      })
+    );
+  }
+  await sendAndConfirmTransaction(connection, createAccounts, [
+    payer,
+    market,
+    requestQueue,
+    eventQueue,
+    bids,
+    asks,
+  ]);
+
+  const u64 = (value: number) =>
+    new anchor.BN(value).toArrayLike(Buffer, "le", 8);
+  // MarketInstruction::InitializeMarket, version 0 tag 0
+  const data = Buffer.concat([
+    Buffer.from([0, 0, 0, 0, 0]),
+    u64(1_000_000), // coin lot size
+    u64(1), // pc lot size
+    Buffer.from([0, 0]), // fee rate bps
+    u64(vaultSignerNonce),
+    u64(100), // pc dust threshold
+  ]);
+
+  const initializeMarket = new TransactionInstruction({
+    programId: OPENBOOK_PROGRAM_ID,
+    keys: [
+      { pubkey: market.publicKey, isSigner: false, isWritable: true },
+      { pubkey: requestQueue.publicKey, isSigner: false, isWritable: true },
+      { pubkey: eventQueue.publicKey, isSigner: false, isWritable: true },
+      { pubkey: bids.publicKey, isSigner: false, isWritable: true },
+      { pubkey: asks.publicKey, isSigner: false, isWritable: true },
+      { pubkey: baseVault, isSigner: false, isWritable: true },
+      { pubkey: quoteVault, isSigner: false, isWritable: true },
+      { pubkey: baseMint, isSigner: false, isWritable: false },
+      { pubkey: NATIVE_MINT, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(initializeMarket),
+    [payer]
+  );
+
+  return market.publicKey;
+}
+
+const orcaTickArrayStartIndex = (tickIndex: number) => {
+  const ticksInArray = ORCA_TICK_ARRAY_SIZE * ORCA_TICK_SPACING;
+  return Math.floor(tickIndex / ticksInArray) * ticksInArray;
+};
+
+async function graduateIntoCpmm(symbol: string, tokenProgram: PublicKey) {
+  const { creator, mint, tokenVault } = await launchToTarget(
+    symbol,
+    { raydiumCpmm: {} },
+    tokenProgram
+  );
+  const { common, cranker } = await prepareMigration(
+    creator,
+    mint,
+    tokenVault,
+    tokenProgram
+  );
+
+  // kept until graduation, the test validator doesn't revoke it at creation
+  const launchedMint = await getMint(
+    connection,
+    mint,
+    "confirmed",
+    tokenProgram
+  );
+  expect(launchedMint.freezeAuthority.equals(tokenStatePda(mint))).to.be.true;
+
+  const [token0Mint, token1Mint] = sortMints(mint, NATIVE_MINT);
+  const poolState = pda(
+    [
+      Buffer.from("pool"),
+      CPMM_AMM_CONFIG.toBuffer(),
+      token0Mint.toBuffer(),
+      token1Mint.toBuffer(),
+    ],
+    RAYDIUM_CPMM_PROGRAM_ID
+  );
+  const lpMint = pda(
+    [Buffer.from("pool_lp_mint"), poolState.toBuffer()],
+    RAYDIUM_CPMM_PROGRAM_ID
+  );
+  const vault = (tokenMint: PublicKey) =>
+    pda(
+      [Buffer.from("pool_vault"), poolState.toBuffer(), tokenMint.toBuffer()],
+      RAYDIUM_CPMM_PROGRAM_ID
+    );
+
+  await program.methods
+    .executeMigration({ time: null })
+    .accountsPartial({
+      common,
+      ammConfig: CPMM_AMM_CONFIG,
+      poolState,
+      lpMint,
+      poolCreatorLpToken: getAssociatedTokenAddressSync(
+        lpMint,
+        poolCreatorPda(mint),
+        true
+      ),
+      creatorLpToken: getAssociatedTokenAddressSync(lpMint, creator.publicKey),
+      token0Vault: vault(token0Mint),
+      token1Vault: vault(token1Mint),
+      observationState: pda(
+        [Buffer.from("observation"), poolState.toBuffer()],
+        RAYDIUM_CPMM_PROGRAM_ID
+      ),
+    })
+    .preInstructions([computeUnits(400_000)])
+    .signers([cranker])
+    .rpc();
+
+  await expectMigrated(
+    mint,
+    poolState,
+    RAYDIUM_CPMM_PROGRAM_ID,
+    tokenProgram
+  );
+  const creatorLp = await getAccount(
+    connection,
+    getAssociatedTokenAddressSync(lpMint, creator.publicKey)
+  );
+  expect(creatorLp.amount.toString()).to.not.equal("0");
+}
+
+describe("migration venues", () => {
+  it("graduates into a Raydium CPMM pool", async () => {
+    await graduateIntoCpmm("VCPMM", TOKEN_PROGRAM_ID);
+  });
+
+  it("graduates a Token-2022 launch into a Raydium CPMM pool", async () => {
+    await graduateIntoCpmm("VCPMM22", TOKEN_2022_PROGRAM_ID);
+  });
+
+  it("graduates into a Raydium AMM v4 pool", async () => {
+    const { creator, mint, tokenVault } = await launchToTarget("VAMM", {
+      raydiumAmmV4: {},
+    });
+    const { common, cranker } = await prepareMigration(
+      creator,
+      mint,
+      tokenVault
+    );
+    const market = await createOpenBookMarket(creator, mint);
+
+    const marketPda = (seed: string) =>
+      pda(
+        [
+          RAYDIUM_AMM_V4_PROGRAM_ID.toBuffer(),
+          market.toBuffer(),
+          Buffer.from(seed),
+        ],
+        RAYDIUM_AMM_V4_PROGRAM_ID
+      );
+    const [ammAuthority, nonce] = PublicKey.findProgramAddressSync(
+      [Buffer.from("amm authority")],
+      RAYDIUM_AMM_V4_PROGRAM_ID
+    );
+    const ammPool = marketPda("amm_associated_seed");
+    const ammLpMint = marketPda("lp_mint_associated_seed");
+
+    const executeMigration = (poolAccount: PublicKey) =>
+      program.methods
+        .executeMigrationRaydiumAmm({ nonce, time: null })
+        .accountsPartial({
+          common,
+          ammPool: poolAccount,
+          ammAuthority,
+          ammOpenOrders: marketPda("open_order_associated_seed"),
+          ammLpMint,
+          ammCoinVault: marketPda("coin_vault_associated_seed"),
+          ammPcVault: marketPda("pc_vault_associated_seed"),
+          ammTargetOrders: marketPda("target_associated_seed"),
+          ammConfig: pda(
+            [Buffer.from("amm_config_account_seed")],
+            RAYDIUM_AMM_V4_PROGRAM_ID
+          ),
+          createFeeDestination: RAYDIUM_AMM_V4_FEE_DESTINATION,
+          marketProgram: OPENBOOK_PROGRAM_ID,
+          market,
+          poolCreatorLpToken: getAssociatedTokenAddressSync(
+            ammLpMint,
+            poolCreatorPda(mint),
+            true
+          ),
+          creatorLpToken: getAssociatedTokenAddressSync(
+            ammLpMint,
+            creator.publicKey
+          ),
+        })
+        .preInstructions([computeUnits(400_000)])
+        .signers([cranker])
+        .rpc();
+
+    // the recorded pool must be the one derived for the market
+    try {
+      await executeMigration(Keypair.generate().publicKey);
+      expect.fail("a pool off the derived address should be rejected");
+    } catch (err) {
+      expect(err).to.be.instanceOf(anchor.AnchorError);
+      expect((err as anchor.AnchorError).error.errorCode.code).to.equal(
+        "InvalidPoolAddress"
+      );
+    }
+
+    await executeMigration(ammPool);
+
+    await expectMigrated(mint, ammPool, RAYDIUM_AMM_V4_PROGRAM_ID);
+    const creatorLp = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(ammLpMint, creator.publicKey)
+    );
+    expect(creatorLp.amount.toString()).to.not.equal("0");
+  });
+
+  it("graduates into an Orca whirlpool", async () => {
+    const { creator, mint, tokenVault } = await launchToTarget("VORCA", {
+      orcaWhirlpool: {},
+    });
+    const { common, cranker } = await prepareMigration(
+      creator,
+      mint,
+      tokenVault
+    );
+
+    const [tokenMintA, tokenMintB] = sortMints(mint, NATIVE_MINT);
+    const tickSpacing = Buffer.alloc(2);
+    tickSpacing.writeUInt16LE(ORCA_TICK_SPACING, 0);
+    const whirlpool = pda(
+      [
+        Buffer.from("whirlpool"),
+        ORCA_WHIRLPOOLS_CONFIG.toBuffer(),
+        tokenMintA.toBuffer(),
+        tokenMintB.toBuffer(),
+        tickSpacing,
+      ],
+      ORCA_WHIRLPOOL_PROGRAM_ID
+    );
+    const tickArray = (tickIndex: number) =>
+      pda(
+        [
+          Buffer.from("tick_array"),
+          whirlpool.toBuffer(),
+          Buffer.from(orcaTickArrayStartIndex(tickIndex).toString()),
+        ],
+        ORCA_WHIRLPOOL_PROGRAM_ID
+      );
+    const tickUpperIndex =
+      Math.floor(ORCA_MAX_TICK_INDEX / ORCA_TICK_SPACING) * ORCA_TICK_SPACING;
+
+    const tokenVaultA = Keypair.generate();
+    const tokenVaultB = Keypair.generate();
+    const positionMint = Keypair.generate();
+    const creatorPositionTokenAccount = getAssociatedTokenAddressSync(
+      positionMint.publicKey,
+      creator.publicKey
+    );
+
+    const execute = await program.methods
+      .executeMigrationOrcaWhirlpool({ tickSpacing: ORCA_TICK_SPACING })
+      .accountsPartial({
+        common,
+        whirlpoolsConfig: ORCA_WHIRLPOOLS_CONFIG,
+        feeTier: pda(
+          [
+            Buffer.from("fee_tier"),
+            ORCA_WHIRLPOOLS_CONFIG.toBuffer(),
+            tickSpacing,
+          ],
+          ORCA_WHIRLPOOL_PROGRAM_ID
+        ),
+        whirlpool,
+        tokenVaultA: tokenVaultA.publicKey,
+        tokenVaultB: tokenVaultB.publicKey,
+        position: pda(
+          [Buffer.from("position"), positionMint.publicKey.toBuffer()],
+          ORCA_WHIRLPOOL_PROGRAM_ID
+        ),
+        positionMint: positionMint.publicKey,
+        positionTokenAccount: getAssociatedTokenAddressSync(
+          positionMint.publicKey,
+          poolCreatorPda(mint),
+          true
+        ),
+        creatorPositionTokenAccount,
+        tickArrayLower: tickArray(-tickUpperIndex),
+        tickArrayUpper: tickArray(tickUpperIndex),
+      })
+      .instruction();
+
+    // four signers and the pool accounts don't fit a legacy transaction
+    await sendWithLookupTable(
+      [computeUnits(600_000), execute],
+      [cranker, tokenVaultA, tokenVaultB, positionMint]
+    );
+
+    await expectMigrated(mint, whirlpool, ORCA_WHIRLPOOL_PROGRAM_ID);
+    // opened by the pool creator, the position NFT ends up with the creator
+    const position = await getAccount(connection, creatorPositionTokenAccount);
+    expect(position.amount.toString()).to.equal("1");
+  });
+
+  it("graduates into a Meteora DAMM v2 pool", async () => {
+    const { creator, mint, tokenVault } = await launchToTarget("VDAMM", {
+      meteoraDamm: {},
+    });
+    const { common, cranker } = await prepareMigration(
+      creator,
+      mint,
+      tokenVault
+    );
+
+    const config = pda(
+      [Buffer.from("config"), Buffer.alloc(8)], // config index 0
+      METEORA_DAMM_PROGRAM_ID
+    );
+    // damm v2 pools are keyed by (larger mint, smaller mint)
+    const [smallerMint, largerMint] = sortMints(mint, NATIVE_MINT);
+    const pool = pda(
+      [
+        Buffer.from("pool"),
+        config.toBuffer(),
+        largerMint.toBuffer(),
+        smallerMint.toBuffer(),
+      ],
+      METEORA_DAMM_PROGRAM_ID
+    );
+    const tokenVaultOf = (tokenMint: PublicKey) =>
+      pda(
+        [Buffer.from("token_vault"), tokenMint.toBuffer(), pool.toBuffer()],
+        METEORA_DAMM_PROGRAM_ID
+      );
+    const positionNftMint = Keypair.generate();
+    const positionNftAccount = pda(
+      [
+        Buffer.from("position_nft_account"),
+        positionNftMint.publicKey.toBuffer(),
+      ],
+      METEORA_DAMM_PROGRAM_ID
+    );
+
+    const executeMigration = (poolAccount: PublicKey) =>
+      program.methods
+        .executeMigrationMeteoraDamm({ activationPoint: null })
+        .accountsPartial({
+          common,
+          config,
+          poolAuthority: pda(
+            [Buffer.from("pool_authority")],
+            METEORA_DAMM_PROGRAM_ID
+          ),
+          pool: poolAccount,
+          positionNftMint: positionNftMint.publicKey,
+          positionNftAccount,
+          position: pda(
+            [Buffer.from("position"), positionNftMint.publicKey.toBuffer()],
+            METEORA_DAMM_PROGRAM_ID
+          ),
+          tokenAVault: tokenVaultOf(mint),
+          tokenBVault: tokenVaultOf(NATIVE_MINT),
+          eventAuthority: pda(
+            [Buffer.from("__event_authority")],
+            METEORA_DAMM_PROGRAM_ID
+          ),
+        })
+        .preInstructions([computeUnits(400_000)])
+        .signers([cranker, positionNftMint])
+        .rpc();
+
+    // the recorded pool must be the one derived for the pair
+    try {
+      await executeMigration(Keypair.generate().publicKey);
+      expect.fail("a pool off the derived address should be rejected");
+    } catch (err) {
+      expect(err).to.be.instanceOf(anchor.AnchorError);
+      expect((err as anchor.AnchorError).error.errorCode.code).to.equal(
+        "InvalidPoolAddress"
+      );
+    }
+
+    await executeMigration(pool);
+
+    await expectMigrated(mint, pool, METEORA_DAMM_PROGRAM_ID);
+    // damm v2 position NFTs are Token-2022 mints
+    const positionNft = await getAccount(
+      connection,
+      positionNftAccount,
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(positionNft.amount.toString()).to.equal("1");
+  });
+});
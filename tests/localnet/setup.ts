@@ -0,0 +1,320 @@
+// Shared setup of the tests running against a local validator. Start it with the
+// programs and accounts cloned from devnet (see [test.validator] in Anchor.toml):
+//
+//   anchor localnet
+//   anchor run test-localnet --provider.cluster localnet
+
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  getAssociatedTokenAddressSync,
+  NATIVE_MINT,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  AddressLookupTableAccount,
+  AddressLookupTableProgram,
+  ComputeBudgetProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from "@solana/web3.js";
+import { createHash } from "crypto";
+import { NottyTerminal } from "../../target/types/notty_terminal";
+
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+
+export const program = anchor.workspace.nottyTerminal as Program<NottyTerminal>;
+export const connection = provider.connection;
+export const admin = (provider.wallet as anchor.Wallet).payer;
+
+export const TARGET_SOL = new anchor.BN(450 * LAMPORTS_PER_SOL);
+export const START_MCAP = new anchor.BN(50 * LAMPORTS_PER_SOL);
+export const TOTAL_SUPPLY = new anchor.BN(1_000_000_000);
+export const BASE_UNITS_PER_TOKEN = new anchor.BN(1_000_000_000);
+
+// devnet Raydium CPMM 25 bps config, the program is built with the devnet feature
+export const CPMM_AMM_CONFIG = new PublicKey(
+  "9zSzfkYy6awexsHvmggeH36pfVUdDGyCcwmjT3AQPBj6"
+);
+
+export const pda = (
+  seeds: (Buffer | Uint8Array)[],
+  programId: PublicKey = program.programId
+) => PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+export const globalStatePda = () => pda([Buffer.from("global_state")]);
+
+export const tokenStatePda = (mint: PublicKey) =>
+  pda([Buffer.from("token_state"), mint.toBuffer()]);
+
+// creates the pool and holds the staged liquidity during execute_migration*
+export const poolCreatorPda = (mint: PublicKey) =>
+  pda([Buffer.from("pool_creator"), mint.toBuffer()]);
+
+export const symbolRegistryPda = (symbol: string) =>
+  pda([
+    Buffer.from("symbol_registry"),
+    createHash("sha256").update(symbol.toUpperCase()).digest(),
+  ]);
+
+export async function fundedWallet(sol: number): Promise<Keypair> {
+  const wallet = Keypair.generate();
+  const signature = await connection.requestAirdrop(
+    wallet.publicKey,
+    sol * LAMPORTS_PER_SOL
+  );
+  await connection.confirmTransaction(signature, "confirmed");
+  return wallet;
+}
+
+// Initializes the global state once per validator, with every venue allowed
+export async function ensureGlobalState() {
+  if (await connection.getAccountInfo(globalStatePda())) {
+    return;
+  }
+
+  await program.methods
+    .initialize({
+      listingFeeLamport: new anchor.BN(50_000_000),
+      tradingFeeBps: 150,
+      migrationFeeLamport: new anchor.BN(150_000_000),
+      ammConfig: CPMM_AMM_CONFIG,
+    })
+    .accounts({ admin: admin.publicKey })
+    .rpc();
+
+  await program.methods
+    .updateGlobalState({
+      allowedVenues: [
+        { raydiumCpmm: {} },
+        { raydiumAmmV4: {} },
+        { orcaWhirlpool: {} },
+        { meteoraDamm: {} },
+      ],
+      allowedAmmConfigs: null,
+      rentReceiver: null,
+      revokeFreezeAtCreation: null,
+      metadataPolicy: null,
+      minTotalSupply: null,
+      maxTotalSupply: null,
+      verifiedListingFeeLamport: null,
+      listingFeePlatformBps: null,
+      launchRateLimit: null,
+      rateLimitExempt: null,
+      unwindDelay: null,
+    })
+    .accounts({ admin: admin.publicKey })
+    .rpc();
+}
+
+export function createTokenArgs(symbol: string, overrides: object = {}) {
+  return {
+    name: `Notty ${symbol}`,
+    tokenSymbol: symbol,
+    tokenUri: "https://example.com/token.json",
+    totalSupply: TOTAL_SUPPLY,
+    decimals: null,
+    startMcap: START_MCAP,
+    targetSol: TARGET_SOL,
+    venue: null,
+    initialBuyAmount: null,
+    maxSolCost: new anchor.BN(0),
+    devBuyVesting: null,
+    profile: null,
+    tradingStartsAt: null,
+    allowlist: null,
+    graduationDeadline: null,
+    ...overrides,
+  };
+}
+
+// Optional accounts of the create instructions a plain launch leaves out
+export const noOptionalLaunchAccounts = {
+  creatorVesting: null,
+  vestingVault: null,
+  tokenProfile: null,
+  creatorVerification: null,
+};
+
+// Launches a token graduating into `venue` and buys the curve up to its target,
+// ready for prepare_migration. `tokenProgram` picks spl token or Token-2022
+export async function launchToTarget(
+  symbol: string,
+  venue: object,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+) {
+  await ensureGlobalState();
+
+  const creator = await fundedWallet(50);
+  const buyer = await fundedWallet(600);
+  const mint = Keypair.generate();
+
+  const createToken = tokenProgram.equals(TOKEN_2022_PROGRAM_ID)
+    ? program.methods.createToken2022
+    : program.methods.createToken;
+  await createToken(createTokenArgs(symbol, { venue }))
+    .accountsPartial({
+      launch: {
+        creator: creator.publicKey,
+        creatorMint: mint.publicKey,
+        symbolRegistry: symbolRegistryPda(symbol),
+        tokenProgram,
+        ...noOptionalLaunchAccounts,
+      },
+      creatorMint: mint.publicKey,
+      tokenProgram,
+    })
+    .signers([creator, mint])
+    .rpc();
+
+  const tokenVault = getAssociatedTokenAddressSync(
+    mint.publicKey,
+    tokenStatePda(mint.publicKey),
+    true,
+    tokenProgram
+  );
+
+  // 9.1M tokens at the opening price raise 455 SOL, just past the target
+  await program.methods
+    .purchaseToken({
+      amount: new anchor.BN(9_100_000).mul(BASE_UNITS_PER_TOKEN),
+      maxSolCost: new anchor.BN(600 * LAMPORTS_PER_SOL),
+      allowlistProof: null,
+    })
+    .accounts({
+      user: buyer.publicKey,
+      creatorMint: mint.publicKey,
+      tokenProgram,
+      tokenVault,
+    })
+    .signers([buyer])
+    .rpc();
+
+  return { creator, mint: mint.publicKey, tokenVault };
+}
+
+// Runs prepare_migration and returns the `common` accounts of the
+// execute_migration instructions, signed by a cranker rather than the creator
+export async function prepareMigration(
+  creator: Keypair,
+  mint: PublicKey,
+  tokenVault: PublicKey,
+  launchTokenProgram: PublicKey = TOKEN_PROGRAM_ID
+) {
+  await program.methods
+    .prepareMigration()
+    .accounts({
+      signer: creator.publicKey,
+      tokenMint: mint,
+      tokenVault,
+      wsolMint: NATIVE_MINT,
+      launchTokenProgram,
+    })
+    .signers([creator])
+    .rpc();
+
+  // execute_migration* is permissionless, the pool creator PDA signs the venue
+  const cranker = await fundedWallet(5);
+
+  return {
+    cranker,
+    common: {
+      signer: cranker.publicKey,
+      creator: creator.publicKey,
+      tokenMint: mint,
+      tokenVault,
+      wsolMint: NATIVE_MINT,
+      launchTokenProgram,
+    },
+  };
+}
+
+// Token order of pools keyed by a sorted (token_0, token_1) pair
+export const sortMints = (a: PublicKey, b: PublicKey): [PublicKey, PublicKey] =>
+  a.toBuffer().compare(b.toBuffer()) < 0 ? [a, b] : [b, a];
+
+export const computeUnits = (units: number) =>
+  ComputeBudgetProgram.setComputeUnitLimit({ units });
+
+// Sends the instructions as a v0 transaction with every account but the signers
+// behind a lookup table, for migrations past the legacy transaction size
+export async function sendWithLookupTable(
+  instructions: TransactionInstruction[],
+  signers: Keypair[]
+) {
+  const payer = signers[0];
+  const signerKeys = new Set(
+    signers.map((signer) => signer.publicKey.toBase58())
+  );
+  const addresses = [
+    ...new Set(
+      instructions.reduce(
+        (keys, ix) => [
+          ...keys,
+          ix.programId.toBase58(),
+          ...ix.keys.map((key) => key.pubkey.toBase58()),
+        ],
+        [] as string[]
+      )
+    ),
+  ]
+    .filter((address) => !signerKeys.has(address))
+    .map((address) => new PublicKey(address));
+
+  const [createTable, lookupTable] =
+    AddressLookupTableProgram.createLookupTable({
+      authority: payer.publicKey,
+      payer: payer.publicKey,
+      recentSlot: await connection.getSlot("finalized"),
+    });
+  await sendV0([createTable], signers.slice(0, 1));
+
+  for (let i = 0; i < addresses.length; i += 20) {
+    const extendTable = AddressLookupTableProgram.extendLookupTable({
+      lookupTable,
+      authority: payer.publicKey,
+      payer: payer.publicKey,
+      addresses: addresses.slice(i, i + 20),
+    });
+    await sendV0([extendTable], signers.slice(0, 1));
+  }
+
+  // extended addresses only become usable from the next slot
+  const extendedAt = await connection.getSlot("confirmed");
+  while ((await connection.getSlot("confirmed")) <= extendedAt) {
+    await new Promise((resolve) => setTimeout(resolve, 100));
+  }
+
+  const table = (await connection.getAddressLookupTable(lookupTable)).value;
+  return sendV0(instructions, signers, [table]);
+}
+
+async function sendV0(
+  instructions: TransactionInstruction[],
+  signers: Keypair[],
+  lookupTables: AddressLookupTableAccount[] = []
+) {
+  const { blockhash, lastValidBlockHeight } =
+    await connection.getLatestBlockhash();
+  const message = new TransactionMessage({
+    payerKey: signers[0].publicKey,
+    recentBlockhash: blockhash,
+    instructions,
+  }).compileToV0Message(lookupTables);
+
+  const transaction = new VersionedTransaction(message);
+  transaction.sign(signers);
+
+  const signature = await connection.sendTransaction(transaction);
+  await connection.confirmTransaction(
+    { signature, blockhash, lastValidBlockHeight },
+    "confirmed"
+  );
+  return signature;
+}
@@ -0,0 +1,115 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  ExtensionType,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getExtensionTypes,
+  getMint,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  BASE_UNITS_PER_TOKEN,
+  connection,
+  createTokenArgs,
+  ensureGlobalState,
+  fundedWallet,
+  noOptionalLaunchAccounts,
+  program,
+  symbolRegistryPda,
+  tokenStatePda,
+} from "./setup";
+
+describe("token-2022 launch", () => {
+  before(ensureGlobalState);
+
+  it("creates a Token-2022 mint with a dev buy", async () => {
+    const creator = await fundedWallet(20);
+    const mint = Keypair.generate();
+    const symbol = "T22DEV";
+    // 100k tokens, 5 SOL at the opening price
+    const devBuy = new anchor.BN(100_000).mul(BASE_UNITS_PER_TOKEN);
+
+    await program.methods
+      .createToken2022(
+        createTokenArgs(symbol, {
+          initialBuyAmount: devBuy,
+          maxSolCost: new anchor.BN(6 * LAMPORTS_PER_SOL),
+        })
+      )
+      .accountsPartial({
+        launch: {
+          creator: creator.publicKey,
+          creatorMint: mint.publicKey,
+          symbolRegistry: symbolRegistryPda(symbol),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          ...noOptionalLaunchAccounts,
+        },
+        creatorMint: mint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([creator, mint])
+      .rpc();
+
+    const tokenState = await program.account.tokenState.fetch(
+      tokenStatePda(mint.publicKey)
+    );
+    expect(tokenState.devBuyAmount.eq(devBuy)).to.be.true;
+    expect(tokenState.tokensSold.eq(devBuy)).to.be.true;
+    expect(tokenState.solRaised.gtn(0)).to.be.true;
+
+    const creatorAta = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(
+        mint.publicKey,
+        creator.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(creatorAta.amount.toString()).to.equal(devBuy.toString());
+
+    // venues reject hooked mints, the launch mint only carries its metadata
+    const mintAccount = await getMint(
+      connection,
+      mint.publicKey,
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(getExtensionTypes(mintAccount.tlvData)).to.have.members([
+      ExtensionType.MetadataPointer,
+      ExtensionType.TokenMetadata,
+    ]);
+  });
+
+  it("rejects a Token-2022 launch graduating into an spl token only venue", async () => {
+    const creator = await fundedWallet(5);
+    const mint = Keypair.generate();
+    const symbol = "T22ORCA";
+
+    try {
+      await program.methods
+        .createToken2022(createTokenArgs(symbol, { venue: { orcaWhirlpool: {} } }))
+        .accountsPartial({
+          launch: {
+            creator: creator.publicKey,
+            creatorMint: mint.publicKey,
+            symbolRegistry: symbolRegistryPda(symbol),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            ...noOptionalLaunchAccounts,
+          },
+          creatorMint: mint.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([creator, mint])
+        .rpc();
+      expect.fail("launch should have been rejected");
+    } catch (err) {
+      expect(err).to.be.instanceOf(anchor.AnchorError);
+      expect(err.error.errorCode.code).to.equal("UnsupportedTokenProgram");
+    }
+  });
+});
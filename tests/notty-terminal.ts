@@ -76,6 +76,7 @@ describe("notty-terminal", () => {
         listingFeeLamport: new anchor.BN(50_000_000),
         tradingFeeBps: 150,
         migrationFeeLamport: new anchor.BN(150_000_000),
+        ammConfig: AMM_CONFIG_25BPS,
       })
       .accounts({
         admin: admin_wallet.publicKey,
@@ -164,6 +165,7 @@ describe("notty-terminal", () => {
           maxSolCost: new anchor.BN(2)
             .pow(new anchor.BN(64))
             .sub(new anchor.BN(1)), // 2 ^ 64 - 1 ( for u64 )
+          allowlistProof: null,
         })
         .signers([user_1_wallet])
         .accounts({
@@ -540,18 +542,6 @@ describe("notty-terminal", () => {
         RAYDIUM_CPMM_PROGRAM_ID
       );
 
-      const creatorToken0 = getAssociatedTokenAddressSync(
-        token0Mint,
-        user_1_wallet.publicKey,
-        false,
-        TOKEN_PROGRAM_ID
-      );
-      const creatorToken1 = getAssociatedTokenAddressSync(
-        token1Mint,
-        user_1_wallet.publicKey,
-        false,
-        TOKEN_PROGRAM_ID
-      );
       const creatorLpToken = getAssociatedTokenAddressSync(
         lpMint,
         user_1_wallet.publicKey,
@@ -559,24 +549,44 @@ describe("notty-terminal", () => {
         TOKEN_PROGRAM_ID
       );
 
-      const tx = await program.methods
-        .migrateToRaydium({ tokenMint: tokenMint.publicKey, time: null })
+      const prepareTx = await program.methods
+        .prepareMigration()
         .accounts({
-          ammConfig: AMM_CONFIG_25BPS,
-          creator: user_1_wallet.publicKey,
           signer: user_1_wallet.publicKey,
-          creatorLpToken: creatorLpToken,
-          creatorToken0: creatorToken0,
-          creatorToken1: creatorToken1,
-          token0Mint,
-          token1Mint,
-          token0Program: TOKEN_PROGRAM_ID,
-          token1Program: TOKEN_PROGRAM_ID,
+          tokenMint: tokenMint.publicKey,
           tokenVault: token_vault.address,
           wsolMint: WSOL_MINT,
+          launchTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user_1_wallet])
+        .rpc();
+      console.log("Prepare migration signature: ", prepareTx);
 
-          // @ts-ignore
-          tokenState: token_state,
+      const tx = await program.methods
+        .executeMigration({ time: null })
+        .accounts({
+          common: {
+            signer: user_1_wallet.publicKey,
+            creator: user_1_wallet.publicKey,
+            tokenMint: tokenMint.publicKey,
+            tokenVault: token_vault.address,
+            wsolMint: WSOL_MINT,
+            creatorTokenAccount: getAssociatedTokenAddressSync(
+              tokenMint.publicKey,
+              user_1_wallet.publicKey,
+              false,
+              TOKEN_PROGRAM_ID
+            ),
+            creatorWsolAccount: getAssociatedTokenAddressSync(
+              WSOL_MINT,
+              user_1_wallet.publicKey,
+              false,
+              TOKEN_PROGRAM_ID
+            ),
+            launchTokenProgram: TOKEN_PROGRAM_ID,
+          },
+          ammConfig: AMM_CONFIG_25BPS,
+          creatorLpToken: creatorLpToken,
         })
         .signers([user_1_wallet])
         .rpc();